@@ -3,3 +3,73 @@
 
 #[doc(hidden)]
 extern crate proc_macro;
+
+mod port_list;
+mod ports;
+
+use proc_macro::TokenStream;
+use syn::{DeriveInput, parse_macro_input};
+
+use crate::port_list::PortDefinitions;
+
+/// Derive macro [`Ports`].
+/// Implements the traits `PortProvider` and `PortAccessors` for a struct with port fields.
+///
+/// Port fields are of type `InBoundPort<T>`, `OutBoundPort<T>`, `InOutBoundPort<T>`,
+/// `OutBoundHistoryPort<T>`, `InBoundQueuePort<T>`, `OutBoundQueuePort<T>`,
+/// `LockFreeInBoundPort<T>`, `LockFreeOutBoundPort<T>` or `ComputedPort<T>`.
+///
+/// A port is found by its field name or by the name given with the attribute `#[port(name = "...")]`.
+/// The name of the port itself must be the same, otherwise the port is not found.
+/// Other fields are ignored.
+///
+/// # Usage
+/// ```ignore
+/// #[derive(Ports)]
+/// struct MyNode {
+///     input: InBoundPort<i32>,
+///     #[port(name = "result")]
+///     output: OutBoundPort<f64>,
+///     other_field: String,
+/// }
+/// ```
+#[proc_macro_derive(Ports, attributes(port))]
+pub fn derive_ports(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	ports::derive_ports(&input)
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
+/// Macro [`port_list!`].
+/// Creates a `PortList` from a comma separated list of port definitions.
+/// Port names must be unique, duplicates are rejected at compile time.
+///
+/// A port definition consists of the kind of port (`in`, `inout` or `out`),
+/// the name of the port as identifier or string literal and the type of the port.
+/// `inout` and `out` ports may have an initial value, `in` ports a default value.
+/// An `in` port prefixed with `required` must be bound before use.
+///
+/// # Usage
+/// ```ignore
+/// let list = port_list![in a: i32 = 0, required in f: i32, out b: String, inout c: f64 = 1.0, out "d-e": bool = true];
+/// ```
+#[proc_macro]
+pub fn port_list(input: TokenStream) -> TokenStream {
+	let definitions = parse_macro_input!(input as PortDefinitions);
+	port_list::port_list(&definitions).into()
+}
+
+/// Macro [`port_array!`].
+/// Creates a `PortArray` from a comma separated list of port definitions.
+/// The syntax is the same as for [`port_list!`].
+///
+/// # Usage
+/// ```ignore
+/// let array: PortArray<3> = port_array![in a: i32, out b: String, inout c: f64 = 1.0];
+/// ```
+#[proc_macro]
+pub fn port_array(input: TokenStream) -> TokenStream {
+	let definitions = parse_macro_input!(input as PortDefinitions);
+	port_list::port_array(&definitions).into()
+}
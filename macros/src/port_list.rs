@@ -0,0 +1,149 @@
+// Copyright © 2025 Stephan Kunz
+//! Implementation of the macros `port_list!` and `port_array!`.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+	Expr, Ident, LitStr, Token, Type,
+	parse::{Parse, ParseStream},
+	punctuated::Punctuated,
+};
+
+/// Kind of a port definition.
+enum Kind {
+	In,
+	InOut,
+	Out,
+}
+
+/// A single port definition like `in name: Type`, `required in name: Type` or `out name: Type = value`.
+struct PortDefinition {
+	required: bool,
+	kind: Kind,
+	name: LitStr,
+	ty: Type,
+	value: Option<Expr>,
+}
+
+impl Parse for PortDefinition {
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		let required = input.peek(Ident) && input.fork().parse::<Ident>()? == "required";
+		if required {
+			input.parse::<Ident>()?;
+			if !input.peek(Token![in]) {
+				return Err(input.error("only an `in` port can be required"));
+			}
+		}
+		let kind = if input.peek(Token![in]) {
+			input.parse::<Token![in]>()?;
+			Kind::In
+		} else {
+			let ident: Ident = input.parse()?;
+			match ident.to_string().as_str() {
+				"inout" => Kind::InOut,
+				"out" => Kind::Out,
+				_ => return Err(syn::Error::new_spanned(ident, "expected one of `in`, `inout` or `out`")),
+			}
+		};
+		let name = if input.peek(LitStr) {
+			input.parse::<LitStr>()?
+		} else {
+			let ident: Ident = input.parse()?;
+			LitStr::new(&ident.to_string(), ident.span())
+		};
+		input.parse::<Token![:]>()?;
+		let ty: Type = input.parse()?;
+		let value = if input.peek(Token![=]) {
+			input.parse::<Token![=]>()?;
+			Some(input.parse::<Expr>()?)
+		} else {
+			None
+		};
+		Ok(Self {
+			required,
+			kind,
+			name,
+			ty,
+			value,
+		})
+	}
+}
+
+impl PortDefinition {
+	/// Creates the expression constructing the `Port`.
+	fn to_tokens(&self) -> TokenStream {
+		let name = &self.name;
+		let ty = &self.ty;
+		match (&self.kind, &self.value) {
+			(Kind::In, None) if self.required => {
+				quote! { ::dataport::Port::from(::dataport::InBoundPort::<#ty>::new(#name).required()) }
+			}
+			(Kind::In, Some(value)) if self.required => {
+				quote! { ::dataport::Port::from(::dataport::InBoundPort::<#ty>::with_default(#name, #value).required()) }
+			}
+			(Kind::In, None) => quote! { ::dataport::Port::create_in_port::<#ty>(#name) },
+			(Kind::In, Some(value)) => quote! { ::dataport::Port::create_in_port_with_default::<#ty>(#name, #value) },
+			(Kind::InOut, None) => quote! { ::dataport::Port::create_inout_port::<#ty>(#name) },
+			(Kind::InOut, Some(value)) => {
+				quote! { ::dataport::Port::from(::dataport::InOutBoundPort::<#ty>::with_value(#name, #value)) }
+			}
+			(Kind::Out, None) => quote! { ::dataport::Port::create_out_port::<#ty>(#name) },
+			(Kind::Out, Some(value)) => {
+				quote! { ::dataport::Port::from(::dataport::OutBoundPort::<#ty>::with_value(#name, #value)) }
+			}
+		}
+	}
+}
+
+/// A comma separated list of [`PortDefinition`]s with unique names.
+pub struct PortDefinitions(Vec<PortDefinition>);
+
+impl Parse for PortDefinitions {
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		let definitions = Punctuated::<PortDefinition, Token![,]>::parse_terminated(input)?;
+		let mut result: Vec<PortDefinition> = Vec::new();
+		for definition in definitions {
+			if result
+				.iter()
+				.any(|existing| existing.name.value() == definition.name.value())
+			{
+				return Err(syn::Error::new_spanned(
+					&definition.name,
+					format!("port name '{}' is used more than once", definition.name.value()),
+				));
+			}
+			result.push(definition);
+		}
+		Ok(Self(result))
+	}
+}
+
+/// Creates a `PortList` from the definitions.
+pub fn port_list(definitions: &PortDefinitions) -> TokenStream {
+	if definitions.0.is_empty() {
+		return quote! { ::dataport::PortList::default() };
+	}
+	let ports = definitions
+		.0
+		.iter()
+		.map(PortDefinition::to_tokens);
+	let list = Ident::new("list", Span::mixed_site());
+	quote! {
+		{
+			let mut #list = ::dataport::PortList::default();
+			#(#list.add(#ports);)*
+			#list
+		}
+	}
+}
+
+/// Creates a `PortArray` from the definitions.
+pub fn port_array(definitions: &PortDefinitions) -> TokenStream {
+	let ports = definitions
+		.0
+		.iter()
+		.map(PortDefinition::to_tokens);
+	quote! {
+		::dataport::PortArray::new([#(#ports),*])
+	}
+}
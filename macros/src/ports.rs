@@ -0,0 +1,129 @@
+// Copyright © 2025 Stephan Kunz
+//! Implementation of the derive macro `Ports`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitStr, Type, parse_quote};
+
+/// Names of the port types, that are handled by the derive macro.
+const PORT_TYPES: [&str; 9] = [
+	"ComputedPort",
+	"InBoundPort",
+	"InBoundQueuePort",
+	"InOutBoundPort",
+	"LockFreeInBoundPort",
+	"LockFreeOutBoundPort",
+	"OutBoundHistoryPort",
+	"OutBoundPort",
+	"OutBoundQueuePort",
+];
+
+/// Returns `true` if the type is one of the [`PORT_TYPES`].
+fn is_port_type(ty: &Type) -> bool {
+	if let Type::Path(type_path) = ty
+		&& let Some(segment) = type_path.path.segments.last()
+	{
+		return PORT_TYPES.contains(&segment.ident.to_string().as_str());
+	}
+	false
+}
+
+/// Returns the port name given with the attribute `#[port(name = "...")]`, if there is one.
+fn attribute_name(field: &syn::Field) -> syn::Result<Option<LitStr>> {
+	let mut name = None;
+	for attr in &field.attrs {
+		if attr.path().is_ident("port") {
+			attr.parse_nested_meta(|meta| {
+				if meta.path.is_ident("name") {
+					name = Some(meta.value()?.parse::<LitStr>()?);
+					Ok(())
+				} else {
+					Err(meta.error("unsupported port attribute, expected `name = \"...\"`"))
+				}
+			})?;
+		}
+	}
+	Ok(name)
+}
+
+/// Creates the implementation of `PortProvider` and `PortAccessors`.
+pub fn derive_ports(input: &DeriveInput) -> syn::Result<TokenStream> {
+	let Data::Struct(data) = &input.data else {
+		return Err(syn::Error::new_spanned(input, "derive `Ports` is only possible for structs"));
+	};
+	let Fields::Named(fields) = &data.fields else {
+		return Err(syn::Error::new_spanned(
+			input,
+			"derive `Ports` is only possible for structs with named fields",
+		));
+	};
+
+	let mut names: Vec<LitStr> = Vec::new();
+	let mut idents = Vec::new();
+	for field in &fields.named {
+		let attr_name = attribute_name(field)?;
+		if attr_name.is_none() && !is_port_type(&field.ty) {
+			continue;
+		}
+		let Some(ident) = &field.ident else {
+			continue;
+		};
+		let name = attr_name.unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span()));
+		if names
+			.iter()
+			.any(|existing| existing.value() == name.value())
+		{
+			return Err(syn::Error::new_spanned(
+				&name,
+				format!("port name '{}' is used more than once", name.value()),
+			));
+		}
+		names.push(name);
+		idents.push(ident.clone());
+	}
+
+	let ident = &input.ident;
+	// the values of generic ports must be usable as type erased [`Port`]s
+	let mut generics = input.generics.clone();
+	let params: Vec<_> = generics
+		.type_params()
+		.map(|param| param.ident.clone())
+		.collect();
+	let where_clause = generics.make_where_clause();
+	for param in params {
+		where_clause.predicates.push(parse_quote! {
+			#param: ::core::any::Any + ::core::marker::Send + ::core::marker::Sync
+		});
+	}
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+	let count = names.len();
+	Ok(quote! {
+		#[automatically_derived]
+		impl #impl_generics ::dataport::PortProvider for #ident #ty_generics #where_clause {
+			fn find(
+				&self,
+				name: impl ::core::convert::Into<::dataport::ConstString>,
+			) -> ::core::option::Option<::dataport::Port> {
+				let name: ::dataport::ConstString = name.into();
+				match &*name {
+					#(#names => {
+						let port = ::dataport::Port::from(&self.#idents);
+						// a port named differently than its lookup name is not found
+						(&*::dataport::PortCommons::name(&port) == #names).then_some(port)
+					})*
+					_ => ::core::option::Option::None,
+				}
+			}
+
+			fn provided_ports(&self) -> impl ::core::iter::Iterator<Item = ::dataport::Port> {
+				<[::dataport::Port; #count] as ::core::iter::IntoIterator>::into_iter([
+					#(::dataport::Port::from(&self.#idents),)*
+				])
+			}
+		}
+
+		#[automatically_derived]
+		impl #impl_generics ::dataport::PortAccessors for #ident #ty_generics #where_clause {}
+	})
+}
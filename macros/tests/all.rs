@@ -4,7 +4,10 @@
 #[test]
 fn tests() {
 	let t = trybuild::TestCases::new();
-	//    t.pass("tests/behavior/01-usage.rs");
-	//    t.compile_fail("tests/behavior/02-wrong-usage.rs");
-	//    t.compile_fail("tests/behavior/03-wrong-usage.rs");
+	t.pass("tests/ports/01-usage.rs");
+	t.compile_fail("tests/ports/02-duplicate-name.rs");
+	t.compile_fail("tests/ports/03-wrong-usage.rs");
+	t.pass("tests/port_list/01-usage.rs");
+	t.compile_fail("tests/port_list/02-duplicate-name.rs");
+	t.compile_fail("tests/port_list/03-wrong-usage.rs");
 }
@@ -0,0 +1,34 @@
+// Copyright © 2025 Stephan Kunz
+//! Test correct usage of macros `port_list!` and `port_array!`.
+
+use dataport::{PortAccessors, PortArray, PortIterators, PortList, PortProvider, port_array, port_list};
+
+fn main() {
+	let empty: PortList = port_list![];
+	assert!(empty.is_empty());
+
+	let list = port_list![in a: i32, out b: String, inout c: f64 = 1.0, out "d-e": Vec<i32> = vec![1, 2, 3],];
+	assert_eq!(list.len(), 4);
+	assert!(list.find("a").is_some());
+	assert!(list.find("b").is_some());
+	assert_eq!(list.get::<f64>("c").unwrap(), 1.0);
+	assert_eq!(list.get::<Vec<i32>>("d-e").unwrap(), vec![1, 2, 3]);
+	assert!(list.get::<String>("b").is_err());
+
+	let array: PortArray<3> = port_array![in a: i32, out b: String = "hello", inout c: f64];
+	assert_eq!(array.len(), 3);
+	assert_eq!(array.get::<String>("b").unwrap(), String::from("hello"));
+	assert!(list.bind_to::<i32>("a", &array, "c").is_err());
+	assert!(array.bind_to::<f64>("c", &list, "c").is_ok());
+	assert_eq!(array.get::<f64>("c").unwrap(), 1.0);
+
+	let defaults = port_list![in a: i32 = 42, required in b: i32, required in c: String = "default"];
+	assert_eq!(defaults.get::<i32>("a").unwrap(), 42);
+	assert_eq!(defaults.get::<String>("c").unwrap(), String::from("default"));
+	assert!(defaults.validate().is_err());
+	let source = port_list![out x: i32 = 7, out y: String];
+	assert!(defaults.bind_to::<i32>("b", &source, "x").is_ok());
+	assert!(defaults.bind_to::<String>("c", &source, "y").is_ok());
+	assert!(defaults.validate().is_ok());
+	assert_eq!(defaults.get::<i32>("b").unwrap(), 7);
+}
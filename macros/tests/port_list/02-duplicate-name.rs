@@ -0,0 +1,9 @@
+// Copyright © 2025 Stephan Kunz
+//! Test duplicate port names with macros `port_list!` and `port_array!`.
+
+use dataport::{port_array, port_list};
+
+fn main() {
+	let _list = port_list![in a: i32, out b: String, inout a: f64];
+	let _array = port_array![in a: i32, out "a": String];
+}
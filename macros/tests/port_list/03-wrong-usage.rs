@@ -0,0 +1,10 @@
+// Copyright © 2025 Stephan Kunz
+//! Test wrong usage of macros `port_list!` and `port_array!`.
+
+use dataport::{port_array, port_list};
+
+fn main() {
+	let _list = port_list![input a: i32];
+	let _array = port_array![required out a: i32];
+	let _list = port_list![out b];
+}
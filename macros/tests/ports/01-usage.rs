@@ -0,0 +1,65 @@
+// Copyright © 2025 Stephan Kunz
+//! Test correct usage of derive macro `Ports`.
+
+use dataport::{InBound, InBoundPort, InOutBoundPort, OutBound, OutBoundPort, PortAccessors, PortProvider, Ports};
+
+#[derive(Ports)]
+struct Producer {
+	value: OutBoundPort<i32>,
+	#[port(name = "text")]
+	message: InOutBoundPort<String>,
+	_other_field: f64,
+}
+
+#[derive(Ports)]
+struct Consumer {
+	value: InBoundPort<i32>,
+	#[port(name = "text")]
+	message: InBoundPort<String>,
+}
+
+#[derive(Ports)]
+struct Generic<T> {
+	value: OutBoundPort<T>,
+}
+
+fn main() {
+	let producer = Producer {
+		value: OutBoundPort::new("value"),
+		message: InOutBoundPort::new("text"),
+		_other_field: 1.0,
+	};
+	let consumer = Consumer {
+		value: InBoundPort::new("value"),
+		message: InBoundPort::new("text"),
+	};
+
+	assert!(producer.find("value").is_some());
+	assert!(producer.find("text").is_some());
+	assert!(producer.find("message").is_none());
+	assert!(producer.find("_other_field").is_none());
+
+	assert!(consumer.bind_to::<i32>("value", &producer, "value").is_ok());
+	assert!(consumer.bind_to::<String>("text", &producer, "text").is_ok());
+
+	producer.value.set(42);
+	producer.message.set(String::from("hello world"));
+	assert_eq!(consumer.value.get(), Some(42));
+	assert_eq!(consumer.message.get(), Some(String::from("hello world")));
+	assert_eq!(consumer.get::<i32>("value").unwrap(), 42);
+
+	let generic = Generic::<f64> {
+		value: OutBoundPort::new("value"),
+	};
+	assert!(generic.find("value").is_some());
+	assert!(generic.set::<f64>("value", 1.0).is_ok());
+
+	// a port named differently than its field is not found
+	let mismatched = Producer {
+		value: OutBoundPort::new("other"),
+		message: InOutBoundPort::new("text"),
+		_other_field: 1.0,
+	};
+	assert!(mismatched.find("value").is_none());
+	assert!(mismatched.find("text").is_some());
+}
@@ -0,0 +1,13 @@
+// Copyright © 2025 Stephan Kunz
+//! Test duplicate port names with derive macro `Ports`.
+
+use dataport::{InBoundPort, OutBoundPort, Ports};
+
+#[derive(Ports)]
+struct Node {
+	value: InBoundPort<i32>,
+	#[port(name = "value")]
+	other: OutBoundPort<i32>,
+}
+
+fn main() {}
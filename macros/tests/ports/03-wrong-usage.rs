@@ -0,0 +1,15 @@
+// Copyright © 2025 Stephan Kunz
+//! Test wrong usage of derive macro `Ports`.
+
+use dataport::{InBoundPort, Ports};
+
+#[derive(Ports)]
+struct Tuple(InBoundPort<i32>);
+
+#[derive(Ports)]
+struct Attribute {
+	#[port(alias = "value")]
+	value: InBoundPort<i32>,
+}
+
+fn main() {}
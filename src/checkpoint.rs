@@ -0,0 +1,213 @@
+// Copyright © 2025 Stephan Kunz
+//! Implementation of a [`Checkpoint`] of a [`PortDataBase`].
+
+use core::any::{Any, TypeId};
+
+use alloc::{boxed::Box, collections::btree_map::BTreeMap, string::String, vec::Vec};
+
+use crate::{ConstString, RwLock, port::Port, port_data_base::PortDataBase, port_value::PortValuePtr, traits::PortProvider};
+
+/// Function saving the value of a [`Port`], returns `None` if the port has another type.
+type SaveFn = fn(&Port) -> Option<Box<dyn SavedValue>>;
+
+/// The global registry of functions saving values by the [`TypeId`] of the value.
+static SAVERS: RwLock<BTreeMap<TypeId, SaveFn>> = RwLock::new(BTreeMap::new());
+
+/// A type erased value saved in a [`Checkpoint`].
+trait SavedValue: Send + Sync {
+	/// Reinstates the saved value under `key`.
+	fn restore(&self, db: &mut PortDataBase, key: &ConstString);
+}
+
+/// A saved value of type `T` together with the state of the port it is reinstated in.
+struct Saved<T> {
+	/// The value and its sequence number at saving or at the last restoring.
+	/// Holding the value keeps a recreated value from being mistaken for it.
+	state: RwLock<(PortValuePtr<T>, u32)>,
+	/// The saved value.
+	value: Option<T>,
+}
+
+impl<T: Any + Clone + Send + Sync> Saved<T> {
+	/// Saves the value of `port`, if it has a value of type `T`.
+	fn save(port: &Port) -> Option<Box<dyn SavedValue>> {
+		let value = port.as_in_out_port::<T>()?.value();
+		let (sequence_number, saved) = {
+			let guard = value.read();
+			(guard.sequence_number(), guard.get())
+		};
+		Some(Box::new(Self {
+			state: RwLock::new((value, sequence_number)),
+			value: saved,
+		}))
+	}
+}
+
+impl<T: Any + Clone + Send + Sync> SavedValue for Saved<T> {
+	fn restore(&self, db: &mut PortDataBase, key: &ConstString) {
+		if let Some(port) = db.find(key.clone()) {
+			if let Some(port) = port.as_in_out_port::<T>() {
+				let value = port.value();
+				let sequence_number = value.read().sequence_number();
+				// an unchanged value keeps its sequence number
+				let state = self.state.read();
+				if value.ptr_eq(&state.0) && sequence_number == state.1 {
+					return;
+				}
+			} else {
+				// the key has been recreated with another type
+				db.remove(key);
+			}
+		}
+		// the type matches, as a port with another type has been removed
+		if db.load(key.clone(), self.value.clone()).is_ok()
+			&& let Some(port) = db.find(key.clone())
+			&& let Some(port) = port.as_in_out_port::<T>()
+		{
+			let value = port.value();
+			let sequence_number = value.read().sequence_number();
+			*self.state.write() = (value, sequence_number);
+		}
+	}
+}
+
+/// Returns the builtin save function for the type with `type_id`.
+fn builtin(type_id: TypeId) -> Option<SaveFn> {
+	macro_rules! builtin {
+		($($tp:ty),*) => {
+			$(
+				if type_id == TypeId::of::<$tp>() {
+					return Some(Saved::<$tp>::save);
+				}
+				if type_id == TypeId::of::<Vec<$tp>>() {
+					return Some(Saved::<Vec<$tp>>::save);
+				}
+			)*
+		};
+	}
+	builtin!(
+		bool,
+		char,
+		f32,
+		f64,
+		i8,
+		i16,
+		i32,
+		i64,
+		i128,
+		isize,
+		u8,
+		u16,
+		u32,
+		u64,
+		u128,
+		usize,
+		String,
+		ConstString
+	);
+	None
+}
+
+/// Checkpoint
+///
+/// An opaque copy of the values of a [`PortDataBase`], created by [`PortDataBase::checkpoint`]
+/// and reinstated by [`PortDataBase::restore`].
+pub struct Checkpoint {
+	/// The saved values by key, `None` for values of types, which cannot be copied.
+	values: BTreeMap<ConstString, Option<Box<dyn SavedValue>>>,
+}
+
+impl core::fmt::Debug for Checkpoint {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("Checkpoint")
+			.field("keys", &self.values.keys().collect::<Vec<_>>())
+			.finish_non_exhaustive()
+	}
+}
+
+impl PortDataBase {
+	/// Registers type `T` for copying its values into a [`Checkpoint`].
+	///
+	/// Builtin are `bool`, `char`, the numeric primitives, [`String`] and [`ConstString`],
+	/// and [`Vec`]s of these.
+	pub fn register_checkpoint_type<T: Any + Clone + Send + Sync>() {
+		SAVERS
+			.write()
+			.insert(TypeId::of::<T>(), Saved::<T>::save);
+	}
+
+	/// Returns a [`Checkpoint`] with copies of all values,
+	/// which are of a builtin type or a type registered via [`PortDataBase::register_checkpoint_type`].
+	#[must_use]
+	pub fn checkpoint(&self) -> Checkpoint {
+		let savers = SAVERS.read().clone();
+		let values = self
+			.entries()
+			.map(|(key, port)| {
+				let type_id = port.value_type_id();
+				let saved = savers
+					.get(&type_id)
+					.copied()
+					.or_else(|| builtin(type_id))
+					.and_then(|save| save(port));
+				(key.clone(), saved)
+			})
+			.collect();
+		Checkpoint { values }
+	}
+
+	/// Reinstates the values of the `checkpoint`.
+	/// Keys created since the checkpoint are deleted, deleted keys are created again
+	/// and values changed since the checkpoint or its last restoring are replaced, which increments their sequence number.
+	/// Unchanged values keep their sequence number.
+	///
+	/// Values, which could not be copied into the checkpoint, are left unchanged.
+	pub fn restore(&mut self, checkpoint: &Checkpoint) {
+		let created: Vec<ConstString> = self
+			.entries()
+			.map(|(key, _)| key.clone())
+			.filter(|key| !checkpoint.values.contains_key(key))
+			.collect();
+		for key in &created {
+			self.remove(key);
+		}
+		for (key, saved) in &checkpoint.values {
+			if let Some(saved) = saved {
+				saved.restore(self, key);
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#![allow(clippy::unwrap_used)]
+	use crate::traits::PortAccessors;
+
+	use super::*;
+
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	// check, that the auto traits are available.
+	#[test]
+	const fn normal_types() {
+		is_normal::<Checkpoint>();
+	}
+
+	#[test]
+	fn builtins() {
+		assert!(builtin(TypeId::of::<f64>()).is_some());
+		assert!(builtin(TypeId::of::<Vec<ConstString>>()).is_some());
+		assert!(builtin(TypeId::of::<Vec<Vec<u8>>>()).is_none());
+	}
+
+	#[test]
+	fn unchanged_values() {
+		let mut db = PortDataBase::default();
+		db.create::<i32>("a", 1).unwrap();
+		let checkpoint = db.checkpoint();
+		db.restore(&checkpoint);
+		assert_eq!(db.sequence_number("a").unwrap(), 1);
+		assert_eq!(db.get::<i32>("a").unwrap(), 1);
+	}
+}
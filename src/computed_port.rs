@@ -0,0 +1,365 @@
+// Copyright © 2025 Stephan Kunz
+//! Implementation of a port with a value computed from other ports.
+
+use core::{any::Any, task::Waker};
+
+use alloc::{sync::Arc, vec::Vec};
+
+use crate::{
+	ConstString, RwLock,
+	error::Result,
+	port::{Port, PortDirection},
+	port_changed::PortChanged,
+	port_value::{DerivedValue, Notification, Observer, PortValue, PortValuePtr, PortValueReadGuard, Wakers},
+	sequence_number::SequenceNumber,
+	traits::{PortCommons, PortInfo},
+};
+
+/// Function computing a value from the input [`Port`]s.
+type ComputeFn<T> = Arc<dyn Fn(&[Port]) -> Option<T> + Send + Sync>;
+
+/// The internal state of a [`ComputedPort`].
+struct Computed<T> {
+	/// An identifying name of the port.
+	name: ConstString,
+	/// The last computed value, which is replaced by a new one on each recomputation.
+	/// The lock is only held while accessing the pointer, never by a guard of the computed value.
+	value: RwLock<PortValuePtr<T>>,
+	/// The ports the value is computed from.
+	inputs: Vec<Port>,
+	/// The computation.
+	compute: ComputeFn<T>,
+	/// The state of the computations.
+	state: RwLock<State>,
+}
+
+/// The state of the computations of a [`Computed`] value.
+#[derive(Default)]
+struct State {
+	/// The sequence numbers of the inputs at the last computation.
+	evaluated: Option<Vec<u32>>,
+	/// Whether the last computation delivered a value.
+	is_some: bool,
+	/// Incremented with each recomputation, which changes the value.
+	sequence: SequenceNumber,
+	/// The [`Observer`]s of the computed value.
+	observers: Vec<Observer>,
+	/// The wakers of tasks waiting for a recomputation.
+	wakers: Wakers,
+}
+
+impl<T> Computed<T> {
+	fn new(name: ConstString, inputs: Vec<Port>, compute: ComputeFn<T>) -> Self {
+		Self {
+			name,
+			value: RwLock::new(PortValuePtr::new(PortValue::default())),
+			inputs,
+			compute,
+			state: RwLock::new(State::default()),
+		}
+	}
+
+	/// Returns the sequence numbers of the inputs.
+	fn input_sequence_numbers(&self) -> Vec<u32> {
+		self.inputs
+			.iter()
+			.map(PortCommons::sequence_number)
+			.collect()
+	}
+
+	/// Returns the value, after a recomputation if the sequence number of any input has changed since the last computation.
+	fn current(&self) -> PortValuePtr<T> {
+		if self.state.read().evaluated.as_ref() == Some(&self.input_sequence_numbers()) {
+			return self.value.read().clone();
+		}
+		let notification = {
+			// computing under the lock prevents a value computed from older inputs replacing a newer one
+			let mut state = self.state.write();
+			// another thread may have computed the value in between
+			let current = self.input_sequence_numbers();
+			if state.evaluated.as_ref() == Some(&current) {
+				return self.value.read().clone();
+			}
+			let result = (self.compute)(&self.inputs);
+			state.evaluated = Some(current);
+			if result.is_none() && !state.is_some {
+				return self.value.read().clone();
+			}
+			state.is_some = result.is_some();
+			state.sequence.increment();
+			let mut value = PortValue::default();
+			value.mirror(result, state.sequence.value());
+			*self.value.write() = PortValuePtr::new(value);
+			Notification::new(state.sequence.value(), state.observers.clone(), state.wakers.take())
+		};
+		notification.notify();
+		self.value.read().clone()
+	}
+
+	/// Returns the sequence number of the value, after a recomputation if necessary.
+	fn sequence_number(&self) -> u32 {
+		self.current();
+		self.state.read().sequence.value()
+	}
+}
+
+impl<T: Send + Sync> DerivedValue<T> for Computed<T> {
+	fn value(&self) -> PortValuePtr<T> {
+		self.current()
+	}
+
+	fn address(&self) -> usize {
+		core::ptr::from_ref(self).addr()
+	}
+
+	fn subscribe(&self, observer: Observer) {
+		self.state.write().observers.push(observer);
+	}
+
+	fn unsubscribe(&self, observer: &Observer) {
+		self.state
+			.write()
+			.observers
+			.retain(|item| !item.is(observer));
+	}
+
+	fn poll_change(&self, since: u32, id: usize, waker: &Waker) -> Option<u32> {
+		self.current();
+		let mut state = self.state.write();
+		let sequence_number = state.sequence.value();
+		if sequence_number == since {
+			state.wakers.register(id, waker);
+			None
+		} else {
+			Some(sequence_number)
+		}
+	}
+
+	fn remove_waker(&self, id: usize) {
+		self.state.write().wakers.remove(id);
+	}
+}
+
+/// ComputedPort
+///
+/// An out port with a value computed from the values of other ports, e.g. `distance = f(pose, goal)`.
+/// The value is recomputed lazily when it is accessed, if the sequence number of any input has changed
+/// since the last computation. Each recomputation counts as a change of the value.
+///
+/// In ports bound to a [`ComputedPort`] trigger the recomputation when their value is accessed.
+/// Observers and waiting tasks are notified when the value is recomputed.
+pub struct ComputedPort<T>(Arc<Computed<T>>);
+
+impl<T> Clone for ComputedPort<T> {
+	/// A clone computes its value independently, from the same inputs.
+	fn clone(&self) -> Self {
+		Self(Arc::new(Computed::new(
+			self.0.name.clone(),
+			self.0.inputs.clone(),
+			self.0.compute.clone(),
+		)))
+	}
+}
+
+impl<T> core::fmt::Debug for ComputedPort<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("ComputedPort")
+			.field("name", &self.0.name)
+			.field("inputs", &self.0.inputs)
+			.finish_non_exhaustive()
+	}
+}
+
+impl<T: Any + Send + Sync> PortInfo for ComputedPort<T> {
+	type Value = T;
+	const DIRECTION: PortDirection = PortDirection::Out;
+
+	fn value_address(&self) -> usize {
+		Arc::as_ptr(&self.0).addr()
+	}
+}
+
+impl<T> PortCommons for ComputedPort<T> {
+	fn name(&self) -> ConstString {
+		self.0.name.clone()
+	}
+
+	fn sequence_number(&self) -> u32 {
+		self.0.sequence_number()
+	}
+
+	fn is_bound(&self) -> bool {
+		false
+	}
+
+	fn bound_source_name(&self) -> Option<ConstString> {
+		None
+	}
+}
+
+impl<T> ComputedPort<T> {
+	/// Creates a port named `name` with a value computed by `compute` from the `inputs`.
+	/// The inputs are passed to `compute` in the given order,
+	/// `compute` returns `None` if the value cannot be computed, e.g. because an input has no value.
+	///
+	/// The inputs should share their state with the ports they represent,
+	/// like the [`Port`]s created via `Port::from(&port)` or returned by [`PortProvider::find`](crate::PortProvider::find).
+	/// As `compute` runs while the port is locked, it must not access the port itself.
+	#[must_use]
+	pub fn new(
+		name: impl Into<ConstString>,
+		inputs: impl IntoIterator<Item = Port>,
+		compute: impl Fn(&[Port]) -> Option<T> + Send + Sync + 'static,
+	) -> Self {
+		Self(Arc::new(Computed::new(
+			name.into(),
+			inputs.into_iter().collect(),
+			Arc::new(compute),
+		)))
+	}
+
+	/// Returns a handle sharing the internal state with `self`,
+	/// in contrast to [`Clone`], which creates an independent copy.
+	pub(crate) fn shared(&self) -> Self {
+		Self(self.0.clone())
+	}
+
+	/// Returns the input ports.
+	#[must_use]
+	pub fn inputs(&self) -> &[Port] {
+		&self.0.inputs
+	}
+
+	/// Returns a clone/copy of the computed value.
+	#[must_use]
+	pub fn get(&self) -> Option<T>
+	where
+		T: Clone,
+	{
+		self.0.current().read().get()
+	}
+
+	/// Returns an immutable guard to the computed value.
+	/// # Errors
+	/// - [`Error::NoValueSet`](crate::Error::NoValueSet), if the value could not be computed.
+	pub fn read(&self) -> Result<PortValueReadGuard<T>> {
+		PortValueReadGuard::new(self.0.name.clone(), self.0.current())
+	}
+
+	/// Registers a `callback` for changes of the computed value.
+	/// It is called with the name of the port and the new sequence number, when the value is recomputed.
+	pub fn on_change(&self, callback: impl Fn(&str, u32) + Send + Sync + 'static) {
+		self.0
+			.state
+			.write()
+			.observers
+			.push(Observer::new(self.0.name.clone(), Arc::new(callback)));
+	}
+
+	/// Returns a future, which resolves when the computed value has a sequence number different from `since`.
+	/// As the value is computed lazily, a change of an input is only noticed when polling the future.
+	pub fn wait_for_change(&self, since: u32) -> PortChanged<T>
+	where
+		T: Send + Sync + 'static,
+	{
+		PortChanged::from_derived(self.0.clone(), since)
+	}
+
+	/// Returns the value after a recomputation, if necessary.
+	pub(crate) fn value(&self) -> PortValuePtr<T> {
+		self.0.current()
+	}
+
+	/// Returns the [`DerivedValue`] for binding an in port to this port.
+	pub(crate) fn derived(&self) -> Arc<dyn DerivedValue<T>>
+	where
+		T: Send + Sync + 'static,
+	{
+		self.0.clone()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#![allow(clippy::unwrap_used)]
+	use core::{pin::Pin, task::Context};
+
+	use alloc::{string::String, vec};
+
+	use crate::in_port::InBoundPort;
+
+	use super::*;
+
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	// check, that the auto traits are available.
+	#[test]
+	const fn normal_types() {
+		is_normal::<&ComputedPort<i32>>();
+		is_normal::<ComputedPort<String>>();
+	}
+
+	#[test]
+	fn lazy_computation() {
+		let calls = Arc::new(RwLock::new(0));
+		let counter = calls.clone();
+		let input = InBoundPort::<i32>::with_default("input", 1);
+		let computed = ComputedPort::new("computed", vec![Port::from(&input)], move |inputs| {
+			*counter.write() += 1;
+			inputs[0].get::<i32>().map(|value| value * 2)
+		});
+		assert_eq!(*calls.read(), 0);
+		assert_eq!(computed.get(), Some(2));
+		assert_eq!(computed.get(), Some(2));
+		assert_eq!(computed.sequence_number(), 1);
+		assert_eq!(*calls.read(), 1);
+
+		// a held guard keeps the former value, but does not prevent the recomputation
+		let guard = computed.read().unwrap();
+		input.value().set(2);
+		assert_eq!(computed.get(), Some(4));
+		assert_eq!(computed.sequence_number(), 2);
+		assert_eq!(*guard, 2);
+		drop(guard);
+		assert_eq!(computed.get(), Some(4));
+		assert_eq!(*calls.read(), 2);
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn concurrent_recomputation() {
+		extern crate std;
+		let input = InBoundPort::<i32>::with_default("input", 0);
+		let computed = ComputedPort::new("computed", vec![Port::from(&input)], |inputs| inputs[0].get::<i32>());
+		std::thread::scope(|scope| {
+			scope.spawn(|| {
+				for value in 1..=1_000 {
+					input.value().set(value);
+				}
+			});
+			for _ in 0..2 {
+				scope.spawn(|| {
+					// a value computed from older inputs never replaces a newer one
+					let mut last = 0;
+					for _ in 0..1_000 {
+						let value = computed.get().unwrap();
+						assert!(value >= last);
+						last = value;
+					}
+				});
+			}
+		});
+		assert_eq!(computed.get(), Some(1_000));
+	}
+
+	#[test]
+	fn removed_wakers() {
+		let computed = ComputedPort::new("computed", [], |_| Some(1));
+		let mut changed = computed.wait_for_change(1);
+		let mut cx = Context::from_waker(Waker::noop());
+		assert!(Pin::new(&mut changed).poll(&mut cx).is_pending());
+		assert_eq!(computed.0.state.read().wakers.len(), 1);
+		drop(changed);
+		assert_eq!(computed.0.state.read().wakers.len(), 0);
+	}
+}
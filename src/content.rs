@@ -0,0 +1,291 @@
+// Copyright © 2025 Stephan Kunz
+//! Buffering of self describing serialized data, available with feature `serde`.
+
+use core::marker::PhantomData;
+
+use alloc::{borrow::ToOwned, boxed::Box, string::String, vec::Vec};
+use serde::{
+	Deserialize, Deserializer,
+	de::{
+		self, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, Unexpected, Visitor,
+		value::{MapAccessDeserializer, MapDeserializer, SeqDeserializer},
+	},
+	forward_to_deserialize_any,
+};
+
+/// Any deserialized content, which can be deserialized again into a concrete type.
+///
+/// Used for values, whose type is not known when they are read, e.g. a value read before its type tag.
+/// Requires a self describing data format.
+#[derive(Debug)]
+pub(crate) enum Content {
+	Bool(bool),
+	I64(i64),
+	U64(u64),
+	I128(i128),
+	U128(u128),
+	F64(f64),
+	Char(char),
+	String(String),
+	Bytes(Vec<u8>),
+	None,
+	Some(Box<Content>),
+	Unit,
+	Newtype(Box<Content>),
+	Seq(Vec<Content>),
+	Map(Vec<(Content, Content)>),
+}
+
+impl Content {
+	/// Returns the kind of content for error messages.
+	fn unexpected(&self) -> Unexpected<'_> {
+		match self {
+			Self::Bool(value) => Unexpected::Bool(*value),
+			Self::I64(value) => Unexpected::Signed(*value),
+			Self::U64(value) => Unexpected::Unsigned(*value),
+			Self::I128(_) | Self::U128(_) => Unexpected::Other("128 bit integer"),
+			Self::F64(value) => Unexpected::Float(*value),
+			Self::Char(value) => Unexpected::Char(*value),
+			Self::String(value) => Unexpected::Str(value),
+			Self::Bytes(value) => Unexpected::Bytes(value),
+			Self::None | Self::Some(_) => Unexpected::Option,
+			Self::Unit => Unexpected::Unit,
+			Self::Newtype(_) => Unexpected::NewtypeStruct,
+			Self::Seq(_) => Unexpected::Seq,
+			Self::Map(_) => Unexpected::Map,
+		}
+	}
+}
+
+impl<'de> Deserialize<'de> for Content {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserializer.deserialize_any(ContentVisitor)
+	}
+}
+
+/// Visitor collecting any [`Content`].
+struct ContentVisitor;
+
+impl<'de> Visitor<'de> for ContentVisitor {
+	type Value = Content;
+
+	fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+		formatter.write_str("any value")
+	}
+
+	fn visit_bool<E: de::Error>(self, value: bool) -> Result<Self::Value, E> {
+		Ok(Content::Bool(value))
+	}
+
+	fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+		Ok(Content::I64(value))
+	}
+
+	fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+		Ok(Content::U64(value))
+	}
+
+	fn visit_i128<E: de::Error>(self, value: i128) -> Result<Self::Value, E> {
+		Ok(Content::I128(value))
+	}
+
+	fn visit_u128<E: de::Error>(self, value: u128) -> Result<Self::Value, E> {
+		Ok(Content::U128(value))
+	}
+
+	fn visit_f64<E: de::Error>(self, value: f64) -> Result<Self::Value, E> {
+		Ok(Content::F64(value))
+	}
+
+	fn visit_char<E: de::Error>(self, value: char) -> Result<Self::Value, E> {
+		Ok(Content::Char(value))
+	}
+
+	fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+		Ok(Content::String(value.to_owned()))
+	}
+
+	fn visit_string<E: de::Error>(self, value: String) -> Result<Self::Value, E> {
+		Ok(Content::String(value))
+	}
+
+	fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+		Ok(Content::Bytes(value.to_owned()))
+	}
+
+	fn visit_byte_buf<E: de::Error>(self, value: Vec<u8>) -> Result<Self::Value, E> {
+		Ok(Content::Bytes(value))
+	}
+
+	fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+		Ok(Content::None)
+	}
+
+	fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+		Content::deserialize(deserializer).map(|content| Content::Some(Box::new(content)))
+	}
+
+	fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+		Ok(Content::Unit)
+	}
+
+	fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+		Content::deserialize(deserializer).map(|content| Content::Newtype(Box::new(content)))
+	}
+
+	fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+		let mut elements = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+		while let Some(element) = seq.next_element()? {
+			elements.push(element);
+		}
+		Ok(Content::Seq(elements))
+	}
+
+	fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+		let mut entries = Vec::with_capacity(map.size_hint().unwrap_or_default());
+		while let Some(entry) = map.next_entry()? {
+			entries.push(entry);
+		}
+		Ok(Content::Map(entries))
+	}
+
+	fn visit_enum<A: EnumAccess<'de>>(self, _data: A) -> Result<Self::Value, A::Error> {
+		Err(de::Error::custom("buffering of enums is not supported"))
+	}
+}
+
+/// A [`Deserializer`] for buffered [`Content`].
+pub(crate) struct ContentDeserializer<E> {
+	content: Content,
+	marker: PhantomData<E>,
+}
+
+impl<E> ContentDeserializer<E> {
+	pub(crate) const fn new(content: Content) -> Self {
+		Self {
+			content,
+			marker: PhantomData,
+		}
+	}
+}
+
+impl<E: de::Error> IntoDeserializer<'_, E> for Content {
+	type Deserializer = ContentDeserializer<E>;
+
+	fn into_deserializer(self) -> Self::Deserializer {
+		ContentDeserializer::new(self)
+	}
+}
+
+impl<'de, E: de::Error> Deserializer<'de> for ContentDeserializer<E> {
+	type Error = E;
+
+	fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+		match self.content {
+			Content::Bool(value) => visitor.visit_bool(value),
+			Content::I64(value) => visitor.visit_i64(value),
+			Content::U64(value) => visitor.visit_u64(value),
+			Content::I128(value) => visitor.visit_i128(value),
+			Content::U128(value) => visitor.visit_u128(value),
+			Content::F64(value) => visitor.visit_f64(value),
+			Content::Char(value) => visitor.visit_char(value),
+			Content::String(value) => visitor.visit_string(value),
+			Content::Bytes(value) => visitor.visit_byte_buf(value),
+			Content::None => visitor.visit_none(),
+			Content::Some(content) => visitor.visit_some(Self::new(*content)),
+			Content::Unit => visitor.visit_unit(),
+			Content::Newtype(content) => visitor.visit_newtype_struct(Self::new(*content)),
+			Content::Seq(elements) => {
+				let mut seq = SeqDeserializer::new(elements.into_iter());
+				let value = visitor.visit_seq(&mut seq)?;
+				seq.end()?;
+				Ok(value)
+			}
+			Content::Map(entries) => {
+				let mut map = MapDeserializer::new(entries.into_iter());
+				let value = visitor.visit_map(&mut map)?;
+				map.end()?;
+				Ok(value)
+			}
+		}
+	}
+
+	fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+		match self.content {
+			// formats without an explicit option, like JSON, deliver `null` as unit
+			Content::None | Content::Unit => visitor.visit_none(),
+			Content::Some(content) => visitor.visit_some(Self::new(*content)),
+			_ => visitor.visit_some(self),
+		}
+	}
+
+	fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, E> {
+		match self.content {
+			Content::Newtype(content) => visitor.visit_newtype_struct(Self::new(*content)),
+			_ => visitor.visit_newtype_struct(self),
+		}
+	}
+
+	fn deserialize_enum<V: Visitor<'de>>(
+		self,
+		_name: &'static str,
+		_variants: &'static [&'static str],
+		visitor: V,
+	) -> Result<V::Value, E> {
+		match self.content {
+			Content::String(variant) => visitor.visit_enum(variant.into_deserializer()),
+			Content::Map(entries) if entries.len() == 1 => {
+				visitor.visit_enum(MapAccessDeserializer::new(MapDeserializer::new(entries.into_iter())))
+			}
+			other => Err(de::Error::invalid_type(
+				other.unexpected(),
+				&"a string or a map with a single entry",
+			)),
+		}
+	}
+
+	forward_to_deserialize_any! {
+		bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+		bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#![allow(clippy::unwrap_used)]
+	use alloc::vec;
+	use serde::de::value::{EnumAccessDeserializer, StrDeserializer};
+
+	use super::*;
+
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	// check, that the auto traits are available.
+	#[test]
+	const fn normal_types() {
+		is_normal::<Content>();
+		is_normal::<ContentDeserializer<de::value::Error>>();
+	}
+
+	#[test]
+	fn redeserialize() {
+		let content = Content::Seq(vec![
+			Content::U64(1),
+			Content::Unit,
+			Content::Some(Box::new(Content::I64(-3))),
+		]);
+		let value: Vec<Option<i32>> =
+			Deserialize::deserialize(ContentDeserializer::<de::value::Error>::new(content)).unwrap();
+		assert_eq!(value, vec![Some(1), None, Some(-3)]);
+
+		let content = Content::String(String::from("text"));
+		assert!(u8::deserialize(ContentDeserializer::<de::value::Error>::new(content)).is_err());
+	}
+
+	#[test]
+	fn enums() {
+		// an enum passed as such by the data format cannot be buffered
+		let variant = StrDeserializer::<de::value::Error>::new("Variant");
+		assert!(Content::deserialize(EnumAccessDeserializer::new(variant)).is_err());
+	}
+}
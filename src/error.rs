@@ -1,6 +1,8 @@
 // Copyright © 2025 Stephan Kunz
 //! dataport errors.
 
+use alloc::vec::Vec;
+
 use crate::ConstString;
 
 /// Shortcut for [`dataport`](crate)'s Result<T, E> type
@@ -14,6 +16,12 @@ pub enum Error {
 		/// Name of the port.
 		port: ConstString,
 	},
+	/// A lock-free port does not support an access, which needs a lock on its value,
+	/// like guards, [`PortAccessors::read_many`](crate::PortAccessors::read_many), `replace` and `take`.
+	LockFree {
+		/// Name of the port.
+		port: ConstString,
+	},
 	/// No source for the value of a port set.
 	NoSrcSet {
 		/// Name of the port.
@@ -24,21 +32,72 @@ pub enum Error {
 		/// Name of the port.
 		port: ConstString,
 	},
+	/// The value of a port cannot be set, like the queue of a queue port
+	/// or a value computed by a [`ComputedPort`](crate::ComputedPort) or converted by a binding.
+	NotSettable {
+		/// Name of the port.
+		port: ConstString,
+	},
 	/// A port is already bound.
 	AlreadyBound {
 		/// Name of the port.
 		port: ConstString,
 	},
+	/// A type tag or a type is already registered in a [`TypeRegistry`](crate::TypeRegistry).
+	TagAlreadyRegistered {
+		/// The type tag of the existing registration.
+		tag: ConstString,
+	},
 	/// A port is already defined set of ports.
 	AlreadyExists {
 		/// Name of the port.
 		port: ConstString,
 	},
+	/// There is no conversion between the value types of a binding.
+	NoConversion {
+		/// Name of the port.
+		port: ConstString,
+		/// Name of the source type.
+		from: &'static str,
+		/// Name of the destination type.
+		to: &'static str,
+	},
+	/// A port is not bound.
+	NotBound {
+		/// Name of the port.
+		port: ConstString,
+	},
 	/// No value defined for a port.
 	NoValueSet {
 		/// Name of the port.
 		port: ConstString,
 	},
+	/// Required ports are not bound.
+	RequiredNotBound {
+		/// Names of the ports.
+		ports: Vec<ConstString>,
+	},
+	/// A value for a port could not be parsed.
+	ParseFailed {
+		/// Name of the port.
+		port: ConstString,
+		/// The value to parse.
+		value: ConstString,
+		/// Name of the wanted type.
+		expected: &'static str,
+		/// Description of the failure.
+		reason: ConstString,
+	},
+	/// The queue of a port is full.
+	QueueFull {
+		/// Name of the port.
+		port: ConstString,
+	},
+	/// Waiting for a port timed out.
+	Timeout {
+		/// Name of the port.
+		port: ConstString,
+	},
 	/// Value not initialized for a port.
 	ValueNotInitialized {
 		/// Name of the port.
@@ -48,6 +107,10 @@ pub enum Error {
 	WrongType {
 		/// Name of the port.
 		port: ConstString,
+		/// Name of the wanted type.
+		expected: &'static str,
+		/// Name of the type of the port.
+		actual: &'static str,
 	},
 }
 
@@ -60,11 +123,30 @@ impl core::fmt::Debug for Error {
 			Self::AlreadyBound { port } => write!(f, "AlreadyBound(port: {port})"),
 			Self::AlreadyExists { port } => write!(f, "AlreadyExists(port: {port})"),
 			Self::IsLocked { port } => write!(f, "IsLocked(port: {port})"),
+			Self::LockFree { port } => write!(f, "LockFree(port: {port})"),
 			Self::NoSrcSet { port } => write!(f, "NoSrcSet(port: {port})"),
+			Self::NoConversion { port, from, to } => write!(f, "NoConversion(port: {port}, from: {from}, to: {to})"),
+			Self::NotBound { port } => write!(f, "NotBound(port: {port})"),
 			Self::NotFound { port } => write!(f, "NotFound(port: {port})"),
+			Self::NotSettable { port } => write!(f, "NotSettable(port: {port})"),
 			Self::NoValueSet { port } => write!(f, "NoValueSet(port: {port})"),
+			Self::ParseFailed {
+				port,
+				value,
+				expected,
+				reason,
+			} => write!(
+				f,
+				"ParseFailed(port: {port}, value: {value}, expected: {expected}, reason: {reason})"
+			),
+			Self::QueueFull { port } => write!(f, "QueueFull(port: {port})"),
+			Self::RequiredNotBound { ports } => write!(f, "RequiredNotBound(ports: {ports:?})"),
+			Self::TagAlreadyRegistered { tag } => write!(f, "TagAlreadyRegistered(tag: {tag})"),
+			Self::Timeout { port } => write!(f, "Timeout(port: {port})"),
 			Self::ValueNotInitialized { port } => write!(f, "NoValueSet(port: {port})"),
-			Self::WrongType { port } => write!(f, "WrongType(port: {port})"),
+			Self::WrongType { port, expected, actual } => {
+				write!(f, "WrongType(port: {port}, expected: {expected}, actual: {actual})")
+			}
 		}
 	}
 }
@@ -75,11 +157,41 @@ impl core::fmt::Display for Error {
 			Self::AlreadyBound { port } => write!(f, "port '{port}' is already bound"),
 			Self::AlreadyExists { port } => write!(f, "port '{port}' is already defined"),
 			Self::IsLocked { port } => write!(f, "port '{port}' is currently locked"),
+			Self::LockFree { port } => write!(f, "port '{port}' is lock-free and cannot lock its value"),
 			Self::NoSrcSet { port } => write!(f, "no source set for value of port '{port}'"),
+			Self::NoConversion { port, from, to } => {
+				write!(f, "port '{port}' cannot be bound with a conversion from '{from}' into '{to}'")
+			}
+			Self::NotBound { port } => write!(f, "port '{port}' is not bound"),
 			Self::NotFound { port } => write!(f, "port '{port}' was not found"),
+			Self::NotSettable { port } => write!(f, "value of port '{port}' cannot be set"),
 			Self::NoValueSet { port } => write!(f, "no value set for port '{port}'"),
+			Self::ParseFailed {
+				port,
+				value,
+				expected,
+				reason,
+			} => write!(
+				f,
+				"value '{value}' for port '{port}' cannot be parsed as '{expected}': {reason}"
+			),
+			Self::QueueFull { port } => write!(f, "queue of port '{port}' is full"),
+			Self::RequiredNotBound { ports } => {
+				write!(f, "required ports are not bound:")?;
+				for port in ports {
+					write!(f, " '{port}'")?;
+				}
+				Ok(())
+			}
+			Self::TagAlreadyRegistered { tag } => write!(f, "type tag '{tag}' or its type is already registered"),
+			Self::Timeout { port } => write!(f, "waiting for port '{port}' timed out"),
 			Self::ValueNotInitialized { port } => write!(f, "port {port} hs not been initialized with a value"),
-			Self::WrongType { port } => write!(f, "port: '{port}' has not the wanted type"),
+			Self::WrongType { port, expected, actual } => {
+				write!(
+					f,
+					"port: '{port}' has type '{actual}' instead of the wanted type '{expected}'"
+				)
+			}
 		}
 	}
 }
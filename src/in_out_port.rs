@@ -3,22 +3,26 @@
 
 use core::any::Any;
 
+use alloc::sync::Arc;
+
 use crate::{
 	ConstString, RwLock,
 	error::{Error, Result},
 	out_port::OutBoundPort,
+	port::PortDirection,
+	port_changed::PortChanged,
 	port_data::PortData,
 	port_value::{PortValuePtr, PortValueReadGuard, PortValueWriteGuard},
-	traits::{InBound, InOutBound, OutBound, PortCommons},
+	traits::{InBound, InOutBound, OutBound, PortCommons, PortInfo},
 };
 
 /// InOutBoundPort
 #[repr(transparent)]
-pub struct InOutBoundPort<T>(RwLock<PortData<T>>);
+pub struct InOutBoundPort<T>(Arc<RwLock<PortData<T>>>);
 
 impl<T> Clone for InOutBoundPort<T> {
 	fn clone(&self) -> Self {
-		Self(RwLock::new((*self.0.read()).clone()))
+		Self(Arc::new(RwLock::new((*self.0.read()).clone())))
 	}
 }
 
@@ -45,6 +49,15 @@ impl<T: 'static> PartialEq for InOutBoundPort<T> {
 	}
 }
 
+impl<T: Any> PortInfo for InOutBoundPort<T> {
+	type Value = T;
+	const DIRECTION: PortDirection = PortDirection::InOut;
+
+	fn value_address(&self) -> usize {
+		self.value().address()
+	}
+}
+
 impl<T> PortCommons for InOutBoundPort<T> {
 	fn name(&self) -> ConstString {
 		self.0.read().name()
@@ -53,6 +66,14 @@ impl<T> PortCommons for InOutBoundPort<T> {
 	fn sequence_number(&self) -> u32 {
 		self.0.read().sequence_number()
 	}
+
+	fn is_bound(&self) -> bool {
+		self.0.read().is_bound()
+	}
+
+	fn bound_source_name(&self) -> Option<ConstString> {
+		self.0.read().bound_source_name()
+	}
 }
 
 impl<T> InBound<T> for InOutBoundPort<T> {
@@ -92,25 +113,29 @@ impl<T> InBound<T> for InOutBoundPort<T> {
 			})
 		}
 	}
+
+	fn on_change(&self, callback: impl Fn(&str, u32) + Send + Sync + 'static) {
+		self.0.read().on_change(callback);
+	}
+
+	fn wait_for_change(&self, since: u32) -> PortChanged<T> {
+		self.0.read().wait_for_change(since)
+	}
 }
 
 impl<T> InOutBound<T> for InOutBoundPort<T> {
 	fn replace(&self, value: impl Into<T>) -> Option<T> {
-		self.0
-			.read()
-			.value()
-			.write()
-			.replace(value.into())
+		self.value().replace(value)
 	}
 
 	fn take(&self) -> Option<T> {
-		self.0.read().value().write().take()
+		self.value().take()
 	}
 }
 
 impl<T> OutBound<T> for InOutBoundPort<T> {
 	fn set(&self, value: impl Into<T>) {
-		self.0.read().value().write().set(value.into())
+		self.value().set(value)
 	}
 
 	fn write(&self) -> Result<PortValueWriteGuard<T>> {
@@ -147,36 +172,61 @@ impl<T> OutBound<T> for InOutBoundPort<T> {
 impl<T> InOutBoundPort<T> {
 	#[must_use]
 	pub fn new(name: impl Into<ConstString>) -> Self {
-		Self(RwLock::new(PortData::new(name.into())))
+		Self(Arc::new(RwLock::new(PortData::new(name.into()))))
 	}
 
 	#[must_use]
 	pub fn with_value(name: impl Into<ConstString>, value: impl Into<T>) -> Self {
-		Self(RwLock::new(PortData::with_value(name.into(), value.into())))
+		Self(Arc::new(RwLock::new(PortData::with_value(name.into(), value.into()))))
 	}
 
-	pub(crate) fn value(&self) -> PortValuePtr<T> {
-		self.0.read().value()
+	/// Returns a handle sharing the internal state with `self`,
+	/// in contrast to [`Clone`], which creates a detached copy.
+	pub(crate) fn shared(&self) -> Self {
+		Self(self.0.clone())
 	}
 
-	pub(crate) fn set_value(&self, value: PortValuePtr<T>) {
-		self.0.write().set_value(value);
+	pub(crate) fn value(&self) -> PortValuePtr<T> {
+		self.0.read().value()
 	}
 
+	/// Binds the port to the value of an [`OutBoundPort`].
+	/// # Errors
+	/// - [`Error::AlreadyBound`], if the port is already bound.
 	pub fn bind_to_out_port(&mut self, port: &OutBoundPort<T>) -> Result<()> {
-		if self.value().read().is_some() {
-			return Err(Error::AlreadyBound { port: self.name() });
-		}
-		self.set_value(port.value());
-		Ok(())
+		self.bind(port.name(), port.value())
 	}
 
+	/// Binds the port to the value of an [`InOutBoundPort`].
+	/// # Errors
+	/// - [`Error::AlreadyBound`], if the port is already bound.
 	pub fn bind_to_in_out_port(&mut self, port: &InOutBoundPort<T>) -> Result<()> {
-		if self.value().read().is_some() {
-			return Err(Error::AlreadyBound { port: self.name() });
-		}
-		self.set_value(port.value());
-		Ok(())
+		self.bind(port.name(), port.value())
+	}
+
+	/// Binds the port to the value of an [`OutBoundPort`], replacing an existing binding.
+	pub fn rebind_to_out_port(&mut self, port: &OutBoundPort<T>) {
+		self.rebind(port.name(), port.value());
+	}
+
+	/// Binds the port to the value of an [`InOutBoundPort`], replacing an existing binding.
+	pub fn rebind_to_in_out_port(&mut self, port: &InOutBoundPort<T>) {
+		self.rebind(port.name(), port.value());
+	}
+
+	/// Removes the binding of the port, leaving it with an empty value.
+	/// # Errors
+	/// - [`Error::NotBound`], if the port is not bound.
+	pub fn unbind(&self) -> Result<()> {
+		self.0.write().unbind()
+	}
+
+	pub(crate) fn bind(&self, source: impl Into<ConstString>, value: PortValuePtr<T>) -> Result<()> {
+		self.0.write().bind(source, value)
+	}
+
+	pub(crate) fn rebind(&self, source: impl Into<ConstString>, value: PortValuePtr<T>) {
+		self.0.write().rebind(source, value);
 	}
 }
 
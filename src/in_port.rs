@@ -3,23 +3,29 @@
 
 use core::any::Any;
 
+use alloc::sync::Arc;
+
 use crate::{
 	ConstString, RwLock,
 	error::{Error, Result},
 	in_out_port::InOutBoundPort,
+	out_history_port::OutBoundHistoryPort,
 	out_port::OutBoundPort,
+	port::PortDirection,
+	port_changed::PortChanged,
+	port_conversion::{ConvertFn, Converted},
 	port_data::PortData,
-	port_value::{PortValuePtr, PortValueReadGuard},
-	traits::{InBound, PortCommons},
+	port_value::{DerivedValue, PortValuePtr, PortValueReadGuard},
+	traits::{InBound, PortCommons, PortInfo},
 };
 
 /// InBoundPort
 #[repr(transparent)]
-pub struct InBoundPort<T>(RwLock<PortData<T>>);
+pub struct InBoundPort<T>(Arc<RwLock<PortData<T>>>);
 
 impl<T> Clone for InBoundPort<T> {
 	fn clone(&self) -> Self {
-		Self(RwLock::new((*self.0.read()).clone()))
+		Self(Arc::new(RwLock::new((*self.0.read()).clone())))
 	}
 }
 
@@ -46,6 +52,15 @@ impl<T: 'static> PartialEq for InBoundPort<T> {
 	}
 }
 
+impl<T: Any> PortInfo for InBoundPort<T> {
+	type Value = T;
+	const DIRECTION: PortDirection = PortDirection::In;
+
+	fn value_address(&self) -> usize {
+		self.0.read().value_address()
+	}
+}
+
 impl<T> PortCommons for InBoundPort<T> {
 	fn name(&self) -> ConstString {
 		self.0.read().name()
@@ -54,6 +69,22 @@ impl<T> PortCommons for InBoundPort<T> {
 	fn sequence_number(&self) -> u32 {
 		self.0.read().sequence_number()
 	}
+
+	fn is_bound(&self) -> bool {
+		self.0.read().is_bound()
+	}
+
+	fn bound_source_name(&self) -> Option<ConstString> {
+		self.0.read().bound_source_name()
+	}
+
+	fn is_required(&self) -> bool {
+		self.0.read().is_required()
+	}
+
+	fn has_default(&self) -> bool {
+		self.0.read().has_default()
+	}
 }
 
 impl<T> InBound<T> for InBoundPort<T> {
@@ -93,36 +124,142 @@ impl<T> InBound<T> for InBoundPort<T> {
 			})
 		}
 	}
+
+	fn on_change(&self, callback: impl Fn(&str, u32) + Send + Sync + 'static) {
+		self.0.read().on_change(callback);
+	}
+
+	fn wait_for_change(&self, since: u32) -> PortChanged<T> {
+		self.0.read().wait_for_change(since)
+	}
 }
 
 impl<T> InBoundPort<T> {
 	#[must_use]
 	pub fn new(name: impl Into<ConstString>) -> Self {
-		Self(RwLock::new(PortData::new(name.into())))
+		Self(Arc::new(RwLock::new(PortData::new(name.into()))))
+	}
+
+	/// Creates a port with a `default` value, which is used while the port is not bound.
+	/// The default value does not count as a change.
+	#[must_use]
+	pub fn with_default(name: impl Into<ConstString>, default: impl Into<T>) -> Self
+	where
+		T: Clone + Send + Sync + 'static,
+	{
+		Self(Arc::new(RwLock::new(PortData::with_default(name.into(), default.into()))))
+	}
+
+	/// Marks the port as required to be bound before use.
+	/// Unbound required ports are reported by [`PortIterators::validate`](crate::traits::PortIterators::validate).
+	#[must_use]
+	pub fn required(self) -> Self {
+		self.0.write().set_required();
+		self
+	}
+
+	/// Returns a handle sharing the internal state with `self`,
+	/// in contrast to [`Clone`], which creates a detached copy.
+	pub(crate) fn shared(&self) -> Self {
+		Self(self.0.clone())
 	}
 
 	pub(crate) fn value(&self) -> PortValuePtr<T> {
 		self.0.read().value()
 	}
 
-	pub(crate) fn set_value(&self, value: PortValuePtr<T>) {
-		self.0.write().set_value(value);
+	/// Blocks the current thread until the sequence number differs from `since` and returns the new sequence number.
+	/// A `timeout` of [`Duration::MAX`](core::time::Duration::MAX) waits without a deadline.
+	/// # Errors
+	/// - [`Error::Timeout`], if the sequence number did not change within `timeout`.
+	#[cfg(feature = "std")]
+	pub fn wait_for_update(&self, since: u32, timeout: core::time::Duration) -> Result<u32> {
+		crate::wait::wait_for(self.name(), self.wait_for_change(since), timeout)
 	}
 
+	/// Binds the port to the value of an [`OutBoundPort`].
+	/// # Errors
+	/// - [`Error::AlreadyBound`], if the port is already bound.
 	pub fn bind_to_out_port(&mut self, port: &OutBoundPort<T>) -> Result<()> {
-		if self.value().read().is_some() {
-			return Err(Error::AlreadyBound { port: self.name() });
-		}
-		self.set_value(port.value());
-		Ok(())
+		self.bind(port.name(), port.value())
 	}
 
+	/// Binds the port to the value of an [`InOutBoundPort`].
+	/// # Errors
+	/// - [`Error::AlreadyBound`], if the port is already bound.
 	pub fn bind_to_in_out_port(&mut self, port: &InOutBoundPort<T>) -> Result<()> {
-		if self.value().read().is_some() {
-			return Err(Error::AlreadyBound { port: self.name() });
+		self.bind(port.name(), port.value())
+	}
+
+	/// Binds the port to the value of an [`OutBoundHistoryPort`].
+	/// # Errors
+	/// - [`Error::AlreadyBound`], if the port is already bound.
+	pub fn bind_to_history_port(&mut self, port: &OutBoundHistoryPort<T>) -> Result<()> {
+		self.bind(port.name(), port.value())
+	}
+
+	/// Binds the port to the value of an [`OutBoundPort`], replacing an existing binding.
+	pub fn rebind_to_out_port(&mut self, port: &OutBoundPort<T>) {
+		self.rebind(port.name(), port.value());
+	}
+
+	/// Binds the port to the value of an [`InOutBoundPort`], replacing an existing binding.
+	pub fn rebind_to_in_out_port(&mut self, port: &InOutBoundPort<T>) {
+		self.rebind(port.name(), port.value());
+	}
+
+	/// Removes the binding of the port, leaving it with its default value if it has one, otherwise with an empty value.
+	/// # Errors
+	/// - [`Error::NotBound`], if the port is not bound.
+	pub fn unbind(&self) -> Result<()> {
+		self.0.write().unbind()
+	}
+
+	pub(crate) fn bind(&self, source: impl Into<ConstString>, value: PortValuePtr<T>) -> Result<()> {
+		self.0.write().bind(source, value)
+	}
+
+	pub(crate) fn rebind(&self, source: impl Into<ConstString>, value: PortValuePtr<T>) {
+		self.0.write().rebind(source, value);
+	}
+
+	/// Binds the port to the value of type `S` of the port named `source`, converting it with `convert`.
+	/// # Errors
+	/// - [`Error::AlreadyBound`], if the port is already bound and `rebind` is `false`.
+	pub(crate) fn bind_converted<S: Send + Sync + 'static>(
+		&self,
+		source: impl Into<ConstString>,
+		value: Arc<dyn DerivedValue<S>>,
+		convert: ConvertFn<S, T>,
+		rebind: bool,
+	) -> Result<()>
+	where
+		T: Send + Sync + 'static,
+	{
+		let converted = Converted::new(value, convert);
+		self.bind_derived(source, Arc::new(converted), rebind)
+	}
+
+	/// Returns `true` if the port is bound to a derived value, like a converted or computed one.
+	pub(crate) fn is_derived(&self) -> bool {
+		self.0.read().is_derived()
+	}
+
+	/// Binds the port to the `derived` value of the port named `source`.
+	/// # Errors
+	/// - [`Error::AlreadyBound`], if the port is already bound and `rebind` is `false`.
+	pub(crate) fn bind_derived(
+		&self,
+		source: impl Into<ConstString>,
+		derived: Arc<dyn DerivedValue<T>>,
+		rebind: bool,
+	) -> Result<()> {
+		if rebind {
+			self.0.write().rebind_derived(source, derived);
+			Ok(())
+		} else {
+			self.0.write().bind_derived(source, derived)
 		}
-		self.set_value(port.value());
-		Ok(())
 	}
 }
 
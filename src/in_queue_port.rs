@@ -0,0 +1,195 @@
+// Copyright © 2025 Stephan Kunz
+//! Implementation of an incoming queue port.
+
+use core::any::Any;
+
+use alloc::{sync::Arc, vec::Vec};
+
+use crate::{
+	ConstString, RwLock,
+	error::Result,
+	out_queue_port::OutBoundQueuePort,
+	port::PortDirection,
+	port_data::PortData,
+	port_queue::{self, Queue},
+	port_value::PortValuePtr,
+	traits::{InBound, PortCommons, PortInfo},
+};
+
+/// InBoundQueuePort
+///
+/// Reading pops values from the bounded FIFO queue of the bound [`OutBoundQueuePort`].
+/// An unbound port has no queue and never delivers a value.
+#[repr(transparent)]
+pub struct InBoundQueuePort<T>(Arc<RwLock<PortData<Queue<T>>>>);
+
+impl<T> Clone for InBoundQueuePort<T> {
+	fn clone(&self) -> Self {
+		Self(Arc::new(RwLock::new((*self.0.read()).clone())))
+	}
+}
+
+impl<T> core::fmt::Debug for InBoundQueuePort<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_tuple("InBoundQueuePort")
+			.field(&self.0)
+			.finish()
+	}
+}
+
+impl<T: Any> PortInfo for InBoundQueuePort<T> {
+	type Value = T;
+	const DIRECTION: PortDirection = PortDirection::In;
+
+	fn value_address(&self) -> usize {
+		self.value().address()
+	}
+}
+
+impl<T> PortCommons for InBoundQueuePort<T> {
+	fn name(&self) -> ConstString {
+		self.0.read().name()
+	}
+
+	fn sequence_number(&self) -> u32 {
+		self.0.read().sequence_number()
+	}
+
+	fn is_bound(&self) -> bool {
+		self.0.read().is_bound()
+	}
+
+	fn bound_source_name(&self) -> Option<ConstString> {
+		self.0.read().bound_source_name()
+	}
+}
+
+impl<T> InBoundQueuePort<T> {
+	#[must_use]
+	pub fn new(name: impl Into<ConstString>) -> Self {
+		Self(Arc::new(RwLock::new(PortData::new(name.into()))))
+	}
+
+	/// Removes and returns the oldest value of the queue.
+	#[must_use]
+	pub fn pop(&self) -> Option<T> {
+		port_queue::pop(&self.value())
+	}
+
+	/// Removes and returns all values of the queue, oldest first.
+	#[must_use]
+	pub fn drain(&self) -> Vec<T> {
+		port_queue::drain(&self.value())
+	}
+
+	/// Returns a copy of the oldest value of the queue without removing it.
+	#[must_use]
+	pub fn peek(&self) -> Option<T>
+	where
+		T: Clone,
+	{
+		port_queue::peek(&self.value())
+	}
+
+	/// Returns the number of values in the queue.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		port_queue::len(&self.value())
+	}
+
+	/// Returns `true` if the queue is empty.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Registers a `callback`, which is called after each value pushed into the queue
+	/// with the name of this port and the new sequence number.
+	/// It follows the port when the port is (re)bound.
+	pub fn on_change(&self, callback: impl Fn(&str, u32) + Send + Sync + 'static) {
+		self.0.read().on_change(callback);
+	}
+
+	/// Binds the port to the queue of an [`OutBoundQueuePort`].
+	/// # Errors
+	/// - [`Error::AlreadyBound`](crate::Error::AlreadyBound), if the port is already bound.
+	pub fn bind_to_queue_port(&mut self, port: &OutBoundQueuePort<T>) -> Result<()> {
+		self.bind(port.name(), port.value())
+	}
+
+	/// Binds the port to the queue of an [`OutBoundQueuePort`], replacing an existing binding.
+	pub fn rebind_to_queue_port(&mut self, port: &OutBoundQueuePort<T>) {
+		self.rebind(port.name(), port.value());
+	}
+
+	/// Removes the binding of the port, leaving it without a queue.
+	/// # Errors
+	/// - [`Error::NotBound`](crate::Error::NotBound), if the port is not bound.
+	pub fn unbind(&self) -> Result<()> {
+		self.0.write().unbind()
+	}
+
+	/// Returns a handle sharing the internal state with `self`,
+	/// in contrast to [`Clone`], which creates a detached copy.
+	pub(crate) fn shared(&self) -> Self {
+		Self(self.0.clone())
+	}
+
+	pub(crate) fn value(&self) -> PortValuePtr<Queue<T>> {
+		self.0.read().value()
+	}
+
+	pub(crate) fn bind(&self, source: impl Into<ConstString>, value: PortValuePtr<Queue<T>>) -> Result<()> {
+		self.0.write().bind(source, value)
+	}
+
+	pub(crate) fn rebind(&self, source: impl Into<ConstString>, value: PortValuePtr<Queue<T>>) {
+		self.0.write().rebind(source, value);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#![allow(clippy::unwrap_used)]
+	use alloc::string::String;
+
+	use super::*;
+
+	use crate::port_queue::OverflowPolicy;
+
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	// check, that the auto traits are available.
+	#[test]
+	const fn normal_types() {
+		is_normal::<&InBoundQueuePort<i32>>();
+		is_normal::<InBoundQueuePort<String>>();
+	}
+
+	#[test]
+	fn pop() {
+		let o1 = OutBoundQueuePort::<i32>::new("o1", 3, OverflowPolicy::DropOldest);
+		let mut i1 = InBoundQueuePort::<i32>::new("i1");
+		assert!(i1.pop().is_none());
+		i1.bind_to_queue_port(&o1).unwrap();
+		assert!(i1.bind_to_queue_port(&o1).is_err());
+		assert_eq!(i1.bound_source_name().unwrap().as_ref(), "o1");
+
+		for value in 1..=4 {
+			o1.push(value).unwrap();
+		}
+		assert_eq!(i1.len(), 3);
+		assert_eq!(i1.sequence_number(), 4);
+		assert_eq!(i1.peek(), Some(2));
+		assert_eq!(i1.pop(), Some(2));
+		assert_eq!(i1.drain(), [3, 4]);
+		assert!(i1.is_empty());
+		assert!(o1.is_empty());
+		assert_eq!(i1.sequence_number(), 4);
+
+		i1.unbind().unwrap();
+		o1.push(5).unwrap();
+		assert!(i1.pop().is_none());
+		assert!(i1.unbind().is_err());
+	}
+}
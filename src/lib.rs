@@ -8,38 +8,80 @@ doc_comment::doctest!("../README.md");
 #[doc(hidden)]
 extern crate alloc;
 
+mod checkpoint;
+mod computed_port;
+#[cfg(feature = "serde")]
+mod content;
 mod error;
 mod in_out_port;
 mod in_port;
+mod in_queue_port;
+mod lock;
+mod lock_free_cell;
+mod lock_free_in_port;
+mod lock_free_out_port;
+mod out_history_port;
 mod out_port;
+mod out_queue_port;
 mod port;
 mod port_array;
+mod port_changed;
+mod port_conversion;
 mod port_data;
 mod port_data_base;
+mod port_export;
+mod port_graph;
 mod port_list;
+mod port_name;
+mod port_parser;
+mod port_queue;
 mod port_value;
+mod read_many;
+mod scoped_port_data_base;
 mod sequence_number;
 mod traits;
+mod transaction;
+#[cfg(feature = "serde")]
+mod type_registry;
+#[cfg(feature = "std")]
+mod wait;
 
 use alloc::sync::Arc;
 
-// internal re-export for easy changeability
-use spin::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+// the lock backend is selected by cargo feature
+use lock::RwLock;
 
 /// An immutable thread safe `String` type
 /// see: [Logan Smith](https://www.youtube.com/watch?v=A4cKi7PTJSs).
-type ConstString = Arc<str>;
+pub type ConstString = Arc<str>;
 
 // flatten
+pub use checkpoint::Checkpoint;
+pub use computed_port::ComputedPort;
 pub use error::Error;
 pub use in_out_port::InOutBoundPort;
 pub use in_port::InBoundPort;
+pub use in_queue_port::InBoundQueuePort;
+pub use lock_free_in_port::LockFreeInBoundPort;
+pub use lock_free_out_port::LockFreeOutBoundPort;
+pub use out_history_port::OutBoundHistoryPort;
 pub use out_port::OutBoundPort;
-pub use port::Port;
+pub use out_queue_port::OutBoundQueuePort;
+pub use port::{Port, PortDirection};
 pub use port_array::PortArray;
+pub use port_changed::PortChanged;
 pub use port_data_base::PortDataBase;
+pub use port_export::PortExport;
+pub use port_graph::{GraphIssue, GraphReport, PortGraph};
 pub use port_list::PortList;
+pub use port_name::PortName;
+pub use port_queue::OverflowPolicy;
 pub use port_value::{PortValueReadGuard, PortValueWriteGuard};
-pub use traits::{InBound, InOutBound, OutBound, PortAccessors, PortCommons, PortProvider};
+pub use read_many::{ReadMany, Snapshot};
+pub use scoped_port_data_base::ScopedPortDataBase;
+pub use traits::{InBound, InOutBound, OutBound, PortAccessors, PortCommons, PortIterators, PortProvider};
+pub use transaction::Transaction;
+#[cfg(feature = "serde")]
+pub use type_registry::{SerializablePortDataBase, TypeRegistry};
 // re-exports:
-//pub use dataport_macros::???;
+pub use dataport_macros::{Ports, port_array, port_list};
@@ -0,0 +1,467 @@
+// Copyright © 2025 Stephan Kunz
+//! The reader-writer lock used for ports, with a backend selected by cargo feature.
+//!
+//! The backends in order of precedence, if more than one feature is enabled:
+//! - `lock-parking-lot`: [`parking_lot`](https://docs.rs/parking_lot) raw lock, requires `std`.
+//! - `lock-std`: blocking lock based on [`std::sync::Mutex`] and [`std::sync::Condvar`].
+//!   It favours readers, so a steady stream of readers can starve a writer.
+//! - `lock-critical-section`: spinning lock using [`critical_section`](https://docs.rs/critical-section)
+//!   for single-core MCUs. Only the lock state is changed within a critical section, the value is accessed outside,
+//!   so ports must not be used from interrupt handlers: an interrupt spinning on a lock held by the
+//!   interrupted code would never return.
+//! - `lock-spin`: [`spin`](https://docs.rs/spin) lock, the default, which is also used if no other backend is enabled.
+
+use core::{
+	cell::UnsafeCell,
+	ops::{Deref, DerefMut},
+};
+
+/// The operations a lock backend has to provide.
+///
+/// # Safety
+/// Implementations must guarantee that the lock is either held by any number of readers
+/// or by exactly one writer.
+#[allow(unsafe_code)]
+pub(crate) unsafe trait RawRwLock: Send + Sync {
+	/// An unlocked lock.
+	const INIT: Self;
+
+	/// Acquires a shared lock, blocking until it is available.
+	fn lock_shared(&self);
+
+	/// Tries to acquire a shared lock without blocking.
+	fn try_lock_shared(&self) -> bool;
+
+	/// Releases a shared lock.
+	/// # Safety
+	/// The caller must hold a shared lock.
+	unsafe fn unlock_shared(&self);
+
+	/// Acquires an exclusive lock, blocking until it is available.
+	fn lock_exclusive(&self);
+
+	/// Tries to acquire an exclusive lock without blocking.
+	fn try_lock_exclusive(&self) -> bool;
+
+	/// Releases an exclusive lock.
+	/// # Safety
+	/// The caller must hold the exclusive lock.
+	unsafe fn unlock_exclusive(&self);
+}
+
+#[cfg(feature = "lock-parking-lot")]
+mod backend {
+	use parking_lot::lock_api::RawRwLock as _;
+
+	use super::RawRwLock;
+
+	/// Backend using the raw lock of `parking_lot`.
+	pub(crate) struct Backend(parking_lot::RawRwLock);
+
+	// SAFETY: `parking_lot::RawRwLock` is a reader-writer lock.
+	#[allow(unsafe_code)]
+	unsafe impl RawRwLock for Backend {
+		const INIT: Self = Self(parking_lot::RawRwLock::INIT);
+
+		fn lock_shared(&self) {
+			self.0.lock_shared();
+		}
+
+		fn try_lock_shared(&self) -> bool {
+			self.0.try_lock_shared()
+		}
+
+		#[allow(unsafe_code)]
+		unsafe fn unlock_shared(&self) {
+			// SAFETY: the caller holds a shared lock
+			unsafe { self.0.unlock_shared() }
+		}
+
+		fn lock_exclusive(&self) {
+			self.0.lock_exclusive();
+		}
+
+		fn try_lock_exclusive(&self) -> bool {
+			self.0.try_lock_exclusive()
+		}
+
+		#[allow(unsafe_code)]
+		unsafe fn unlock_exclusive(&self) {
+			// SAFETY: the caller holds the exclusive lock
+			unsafe { self.0.unlock_exclusive() }
+		}
+	}
+}
+
+#[cfg(all(feature = "lock-std", not(feature = "lock-parking-lot")))]
+mod backend {
+	extern crate std;
+
+	use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+
+	use super::RawRwLock;
+
+	/// Backend blocking on a [`Condvar`].
+	/// [`std::sync::RwLock`] itself can not be used, as it can only be unlocked by dropping its guard.
+	///
+	/// Readers are admitted as long as no writer holds the lock, even if a writer is waiting,
+	/// so writers may starve under a continuous read load.
+	pub(crate) struct Backend {
+		/// Number of readers, `-1` if locked by a writer.
+		state: Mutex<isize>,
+		/// Signals an unlock.
+		unlocked: Condvar,
+	}
+
+	impl Backend {
+		fn state(&self) -> MutexGuard<'_, isize> {
+			self.state
+				.lock()
+				.unwrap_or_else(PoisonError::into_inner)
+		}
+
+		fn wait_while(&self, condition: impl FnMut(&mut isize) -> bool) -> MutexGuard<'_, isize> {
+			self.unlocked
+				.wait_while(self.state(), condition)
+				.unwrap_or_else(PoisonError::into_inner)
+		}
+	}
+
+	// SAFETY: the state is only changed under the mutex and checked before.
+	#[allow(unsafe_code)]
+	unsafe impl RawRwLock for Backend {
+		const INIT: Self = Self {
+			state: Mutex::new(0),
+			unlocked: Condvar::new(),
+		};
+
+		fn lock_shared(&self) {
+			*self.wait_while(|state| *state < 0) += 1;
+		}
+
+		fn try_lock_shared(&self) -> bool {
+			let mut state = self.state();
+			if *state < 0 {
+				return false;
+			}
+			*state += 1;
+			true
+		}
+
+		#[allow(unsafe_code)]
+		unsafe fn unlock_shared(&self) {
+			let mut state = self.state();
+			*state -= 1;
+			if *state == 0 {
+				self.unlocked.notify_all();
+			}
+		}
+
+		fn lock_exclusive(&self) {
+			*self.wait_while(|state| *state != 0) = -1;
+		}
+
+		fn try_lock_exclusive(&self) -> bool {
+			let mut state = self.state();
+			if *state != 0 {
+				return false;
+			}
+			*state = -1;
+			true
+		}
+
+		#[allow(unsafe_code)]
+		unsafe fn unlock_exclusive(&self) {
+			*self.state() = 0;
+			self.unlocked.notify_all();
+		}
+	}
+}
+
+#[cfg(all(
+	feature = "lock-critical-section",
+	not(any(feature = "lock-parking-lot", feature = "lock-std"))
+))]
+mod backend {
+	use core::cell::Cell;
+
+	use critical_section::Mutex;
+
+	use super::RawRwLock;
+
+	/// Backend changing its state within a critical section and spinning while locked.
+	///
+	/// The lock is held outside of the critical section, so it must not be acquired in interrupt context:
+	/// on a single core, an interrupt handler spinning on a lock held by the interrupted code deadlocks.
+	pub(crate) struct Backend {
+		/// Number of readers, `-1` if locked by a writer.
+		state: Mutex<Cell<isize>>,
+	}
+
+	impl Backend {
+		fn try_change(&self, f: impl FnOnce(isize) -> Option<isize>) -> bool {
+			critical_section::with(|cs| {
+				let state = self.state.borrow(cs);
+				f(state.get()).map(|new| state.set(new)).is_some()
+			})
+		}
+	}
+
+	// SAFETY: the state is only checked and changed within a critical section.
+	#[allow(unsafe_code)]
+	unsafe impl RawRwLock for Backend {
+		const INIT: Self = Self {
+			state: Mutex::new(Cell::new(0)),
+		};
+
+		fn lock_shared(&self) {
+			while !self.try_lock_shared() {
+				core::hint::spin_loop();
+			}
+		}
+
+		fn try_lock_shared(&self) -> bool {
+			self.try_change(|state| (state >= 0).then_some(state + 1))
+		}
+
+		#[allow(unsafe_code)]
+		unsafe fn unlock_shared(&self) {
+			self.try_change(|state| Some(state - 1));
+		}
+
+		fn lock_exclusive(&self) {
+			while !self.try_lock_exclusive() {
+				core::hint::spin_loop();
+			}
+		}
+
+		fn try_lock_exclusive(&self) -> bool {
+			self.try_change(|state| (state == 0).then_some(-1))
+		}
+
+		#[allow(unsafe_code)]
+		unsafe fn unlock_exclusive(&self) {
+			self.try_change(|_| Some(0));
+		}
+	}
+}
+
+#[cfg(not(any(feature = "lock-parking-lot", feature = "lock-std", feature = "lock-critical-section")))]
+mod backend {
+	use super::RawRwLock;
+
+	/// Backend using a `spin` lock via its `lock_api` interface, whose guards are forgotten.
+	/// Unlike `spin::RwLock::force_write_unlock`, the `lock_api` interface unlocks like a dropped guard,
+	/// which tolerates readers concurrently trying to acquire the lock.
+	pub(crate) struct Backend(spin::lock_api::RwLock<()>);
+
+	// SAFETY: `spin::RwLock` is a reader-writer lock.
+	#[allow(unsafe_code)]
+	unsafe impl RawRwLock for Backend {
+		const INIT: Self = Self(spin::lock_api::RwLock::new(()));
+
+		fn lock_shared(&self) {
+			core::mem::forget(self.0.read());
+		}
+
+		fn try_lock_shared(&self) -> bool {
+			self.0.try_read().map(core::mem::forget).is_some()
+		}
+
+		#[allow(unsafe_code)]
+		unsafe fn unlock_shared(&self) {
+			// SAFETY: the caller holds a shared lock
+			unsafe { self.0.force_unlock_read() }
+		}
+
+		fn lock_exclusive(&self) {
+			core::mem::forget(self.0.write());
+		}
+
+		fn try_lock_exclusive(&self) -> bool {
+			self.0
+				.try_write()
+				.map(core::mem::forget)
+				.is_some()
+		}
+
+		#[allow(unsafe_code)]
+		unsafe fn unlock_exclusive(&self) {
+			// SAFETY: the caller holds the exclusive lock
+			unsafe { self.0.force_unlock_write() }
+		}
+	}
+}
+
+use backend::Backend;
+
+/// A reader-writer lock using the selected [`RawRwLock`] backend.
+pub(crate) struct RwLock<T: ?Sized> {
+	/// The lock.
+	raw: Backend,
+	/// The protected data.
+	data: UnsafeCell<T>,
+}
+
+// SAFETY: the data is only accessed according to the lock.
+#[allow(unsafe_code)]
+unsafe impl<T: ?Sized + Send> Send for RwLock<T> {}
+// SAFETY: the data is only accessed according to the lock.
+#[allow(unsafe_code)]
+unsafe impl<T: ?Sized + Send + Sync> Sync for RwLock<T> {}
+
+impl<T: Default> Default for RwLock<T> {
+	fn default() -> Self {
+		Self::new(T::default())
+	}
+}
+
+impl<T: ?Sized + core::fmt::Debug> core::fmt::Debug for RwLock<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self.try_read() {
+			Some(guard) => f
+				.debug_struct("RwLock")
+				.field("data", &&*guard)
+				.finish(),
+			None => f
+				.debug_struct("RwLock")
+				.field("data", &format_args!("<locked>"))
+				.finish(),
+		}
+	}
+}
+
+impl<T> RwLock<T> {
+	pub(crate) const fn new(data: T) -> Self {
+		Self {
+			raw: Backend::INIT,
+			data: UnsafeCell::new(data),
+		}
+	}
+}
+
+impl<T: ?Sized> RwLock<T> {
+	/// Returns a shared guard, blocking until the lock is available.
+	pub(crate) fn read(&self) -> RwLockReadGuard<'_, T> {
+		self.raw.lock_shared();
+		RwLockReadGuard { lock: self }
+	}
+
+	/// Returns a shared guard, if the lock is available.
+	pub(crate) fn try_read(&self) -> Option<RwLockReadGuard<'_, T>> {
+		self.raw
+			.try_lock_shared()
+			.then(|| RwLockReadGuard { lock: self })
+	}
+
+	/// Returns an exclusive guard, blocking until the lock is available.
+	pub(crate) fn write(&self) -> RwLockWriteGuard<'_, T> {
+		self.raw.lock_exclusive();
+		RwLockWriteGuard { lock: self }
+	}
+
+	/// Returns an exclusive guard, if the lock is available.
+	pub(crate) fn try_write(&self) -> Option<RwLockWriteGuard<'_, T>> {
+		self.raw
+			.try_lock_exclusive()
+			.then(|| RwLockWriteGuard { lock: self })
+	}
+
+	/// Returns the raw lock, for guards which are not bound to a lifetime.
+	pub(crate) const fn raw(&self) -> &impl RawRwLock {
+		&self.raw
+	}
+
+	/// Returns a pointer to the protected data.
+	/// Dereferencing it is only allowed while holding the lock via [`RwLock::raw`].
+	pub(crate) const fn data_ptr(&self) -> *mut T {
+		self.data.get()
+	}
+}
+
+/// Shared guard of a [`RwLock`].
+pub(crate) struct RwLockReadGuard<'a, T: ?Sized> {
+	lock: &'a RwLock<T>,
+}
+
+impl<T: ?Sized> Deref for RwLockReadGuard<'_, T> {
+	type Target = T;
+
+	#[allow(unsafe_code)]
+	fn deref(&self) -> &Self::Target {
+		// SAFETY: the guard holds a shared lock
+		unsafe { &*self.lock.data.get() }
+	}
+}
+
+impl<T: ?Sized> Drop for RwLockReadGuard<'_, T> {
+	#[allow(unsafe_code)]
+	fn drop(&mut self) {
+		// SAFETY: the guard holds a shared lock
+		unsafe { self.lock.raw.unlock_shared() }
+	}
+}
+
+/// Exclusive guard of a [`RwLock`].
+pub(crate) struct RwLockWriteGuard<'a, T: ?Sized> {
+	lock: &'a RwLock<T>,
+}
+
+impl<T: ?Sized> Deref for RwLockWriteGuard<'_, T> {
+	type Target = T;
+
+	#[allow(unsafe_code)]
+	fn deref(&self) -> &Self::Target {
+		// SAFETY: the guard holds the exclusive lock
+		unsafe { &*self.lock.data.get() }
+	}
+}
+
+impl<T: ?Sized> DerefMut for RwLockWriteGuard<'_, T> {
+	#[allow(unsafe_code)]
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		// SAFETY: the guard holds the exclusive lock
+		unsafe { &mut *self.lock.data.get() }
+	}
+}
+
+impl<T: ?Sized> Drop for RwLockWriteGuard<'_, T> {
+	#[allow(unsafe_code)]
+	fn drop(&mut self) {
+		// SAFETY: the guard holds the exclusive lock
+		unsafe { self.lock.raw.unlock_exclusive() }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#![allow(clippy::unwrap_used)]
+	use super::*;
+
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	// check, that the auto traits are available.
+	#[test]
+	const fn normal_types() {
+		is_normal::<&RwLock<i32>>();
+		is_normal::<RwLock<i32>>();
+	}
+
+	#[test]
+	fn locking() {
+		let lock = RwLock::new(1);
+		{
+			let r1 = lock.read();
+			let r2 = lock.try_read().unwrap();
+			assert_eq!(*r1 + *r2, 2);
+			assert!(!lock.raw().try_lock_exclusive());
+		}
+		{
+			let mut w1 = lock.write();
+			*w1 = 2;
+			assert!(lock.try_read().is_none());
+			assert!(!lock.raw().try_lock_exclusive());
+		}
+		assert_eq!(*lock.try_read().unwrap(), 2);
+	}
+}
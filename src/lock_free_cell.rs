@@ -0,0 +1,365 @@
+// Copyright © 2025 Stephan Kunz
+//! Implementation of the buffered [`LockFreeCell`] used by lock-free ports.
+
+use core::{cell::UnsafeCell, task::Waker};
+
+use alloc::vec::Vec;
+use portable_atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering, fence};
+
+use crate::{
+	RwLock,
+	port_value::{Notification, Observer, Wakers},
+};
+
+/// The number of buffers of a [`LockFreeCell`].
+const SLOTS: usize = 4;
+
+/// Flag in the state of a [`Slot`], set while a writer fills it.
+const WRITING: u32 = 1 << 31;
+
+/// The [`Observer`]s and [`Waker`]s of a [`LockFreeCell`].
+#[derive(Default)]
+struct Subscribers {
+	observers: Vec<Observer>,
+	wakers: Wakers,
+}
+
+/// A buffer for a value of a [`LockFreeCell`].
+struct Slot<T> {
+	/// The number of readers copying the value, together with the [`WRITING`] flag.
+	state: AtomicU32,
+	/// The value.
+	value: UnsafeCell<Option<T>>,
+}
+
+impl<T> Slot<T> {
+	const fn new(value: Option<T>) -> Self {
+		Self {
+			state: AtomicU32::new(0),
+			value: UnsafeCell::new(value),
+		}
+	}
+}
+
+/// A cell for `Copy` values, which are accessed without taking a lock.
+///
+/// A writer fills a buffer, which is neither published nor read, and publishes it together with the new
+/// sequence number. A reader pins the published buffer and copies the value, so no value is ever read while
+/// it is written. Both are lock-free, but not wait-free:
+/// - a reader retries, if a write is published while it pins the buffer,
+/// - a writer retries, while all spare buffers are pinned by readers or filled by other writers.
+///
+/// Subscribers are notified by the writer, unless they are locked by another thread,
+/// which then delivers the notification after releasing the lock, so a writer never waits for subscribers.
+/// Notifications may be combined into one with the latest sequence number.
+pub(crate) struct LockFreeCell<T> {
+	/// The buffers.
+	slots: [Slot<T>; SLOTS],
+	/// The sequence number in the upper and the index of the published buffer in the lower half.
+	current: AtomicU64,
+	/// Fast path flag, `true` if there may be subscribers.
+	subscribed: AtomicBool,
+	/// Flag, whether a notification waits for delivery.
+	pending: AtomicBool,
+	/// The subscribers.
+	subscribers: RwLock<Subscribers>,
+	/// Copies the value, captured at creation where `T: Copy` is known, for type erased access.
+	copy: fn(&Self) -> Option<T>,
+}
+
+// SAFETY: a value is only written to a buffer claimed by a single writer, which no reader pins,
+// and only copied from a buffer pinned by readers, which no writer can claim.
+#[allow(unsafe_code)]
+unsafe impl<T: Send + Sync> Sync for LockFreeCell<T> {}
+
+impl<T> core::fmt::Debug for LockFreeCell<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("LockFreeCell")
+			.field("sequence_number", &self.sequence_number())
+			.finish_non_exhaustive()
+	}
+}
+
+/// Returns the sequence number and the buffer index of the packed `current` state.
+#[allow(clippy::cast_possible_truncation)]
+const fn unpack(current: u64) -> (u32, usize) {
+	((current >> 32) as u32, (current as u32) as usize)
+}
+
+/// Returns the packed state of a `sequence_number` and a buffer `index`.
+const fn pack(sequence_number: u32, index: usize) -> u64 {
+	((sequence_number as u64) << 32) | index as u64
+}
+
+impl<T> LockFreeCell<T> {
+	/// Returns the change sequence number of the value.
+	pub(crate) fn sequence_number(&self) -> u32 {
+		unpack(self.current.load(Ordering::SeqCst)).0
+	}
+
+	/// Returns a copy of the value like [`LockFreeCell::get`], without requiring `T: Copy`,
+	/// which is guaranteed by the creation of the cell.
+	pub(crate) fn load(&self) -> Option<T> {
+		(self.copy)(self)
+	}
+
+	/// Adds an [`Observer`].
+	pub(crate) fn subscribe(&self, observer: Observer) {
+		{
+			let mut subscribers = self.subscribers.write();
+			subscribers.observers.push(observer);
+			self.subscribed.store(true, Ordering::SeqCst);
+		}
+		self.deliver();
+	}
+
+	/// Removes an [`Observer`].
+	pub(crate) fn unsubscribe(&self, observer: &Observer) {
+		self.subscribers
+			.write()
+			.observers
+			.retain(|item| !item.is(observer));
+		self.deliver();
+	}
+
+	/// Returns the sequence number if it differs from `since`,
+	/// otherwise registers the [`Waker`] of the future with `id` to be woken on the next change.
+	pub(crate) fn poll_change(&self, since: u32, id: usize, waker: &Waker) -> Option<u32> {
+		let changed = {
+			let mut subscribers = self.subscribers.write();
+			// the flag must be set before checking the sequence number, to not miss a concurrent change
+			self.subscribed.store(true, Ordering::SeqCst);
+			let sequence_number = self.sequence_number();
+			if sequence_number == since {
+				subscribers.wakers.register(id, waker);
+				None
+			} else {
+				Some(sequence_number)
+			}
+		};
+		self.deliver();
+		changed
+	}
+
+	/// Removes the [`Waker`] of the future with `id`.
+	pub(crate) fn remove_waker(&self, id: usize) {
+		self.subscribers.write().wakers.remove(id);
+		self.deliver();
+	}
+
+	/// Sets the value, increments the sequence number and notifies the subscribers.
+	pub(crate) fn set(&self, value: Option<T>) {
+		let mut current = self.current.load(Ordering::SeqCst);
+		// claim a spare buffer, which is neither published nor pinned by a reader
+		let index = loop {
+			let published = unpack(current).1;
+			if let Some(index) = (0..SLOTS).find(|&index| {
+				index != published
+					&& self.slots[index]
+						.state
+						.compare_exchange(0, WRITING, Ordering::Acquire, Ordering::Relaxed)
+						.is_ok()
+			}) {
+				break index;
+			}
+			core::hint::spin_loop();
+			current = self.current.load(Ordering::SeqCst);
+		};
+		let slot = &self.slots[index];
+		// SAFETY: the claimed buffer is accessed by this writer only.
+		#[allow(unsafe_code)]
+		unsafe {
+			*slot.value.get() = value;
+		}
+		// publish the buffer with the next sequence number, while it is still claimed
+		loop {
+			let (previous, _) = unpack(current);
+			let sequence_number = if previous < u32::MAX { previous + 1 } else { 1 };
+			match self
+				.current
+				.compare_exchange(current, pack(sequence_number, index), Ordering::SeqCst, Ordering::SeqCst)
+			{
+				Ok(_) => break,
+				Err(actual) => current = actual,
+			}
+		}
+		slot.state.fetch_sub(WRITING, Ordering::Release);
+
+		if self.subscribed.load(Ordering::SeqCst) {
+			self.pending.store(true, Ordering::SeqCst);
+			self.deliver();
+		}
+	}
+
+	/// Delivers a pending notification.
+	/// If the subscribers are locked by another thread, that thread delivers it after releasing the lock.
+	fn deliver(&self) {
+		// orders the release of the lock or the raising of `pending` before checking the other
+		fence(Ordering::SeqCst);
+		while self.pending.load(Ordering::SeqCst) {
+			let notification = {
+				let Some(mut subscribers) = self.subscribers.try_write() else {
+					return;
+				};
+				if !self.pending.swap(false, Ordering::SeqCst) {
+					return;
+				}
+				if subscribers.observers.is_empty() {
+					self.subscribed.store(false, Ordering::SeqCst);
+				}
+				let wakers = subscribers.wakers.take();
+				Notification::new(self.sequence_number(), subscribers.observers.clone(), wakers)
+			};
+			notification.notify();
+		}
+	}
+}
+
+impl<T: Copy> LockFreeCell<T> {
+	/// Creates a cell, an initial `value` counts as first change.
+	pub(crate) fn new(value: Option<T>) -> Self {
+		let sequence_number = u32::from(value.is_some());
+		Self {
+			slots: [
+				Slot::new(value),
+				Slot::new(None),
+				Slot::new(None),
+				Slot::new(None),
+			],
+			current: AtomicU64::new(pack(sequence_number, 0)),
+			subscribed: AtomicBool::new(false),
+			pending: AtomicBool::new(false),
+			subscribers: RwLock::new(Subscribers::default()),
+			copy: Self::get,
+		}
+	}
+
+	/// Returns a copy of the value.
+	pub(crate) fn get(&self) -> Option<T> {
+		loop {
+			let current = self.current.load(Ordering::SeqCst);
+			let slot = &self.slots[unpack(current).1];
+			// pin the buffer, a writer claims only unpinned buffers
+			let state = slot.state.fetch_add(1, Ordering::Acquire);
+			// the buffer must still be the published one and completely written
+			let value = (state & WRITING == 0 && self.current.load(Ordering::SeqCst) == current).then(|| {
+				// SAFETY: the pinned buffer is not written until it is unpinned.
+				#[allow(unsafe_code)]
+				unsafe {
+					*slot.value.get()
+				}
+			});
+			slot.state.fetch_sub(1, Ordering::Release);
+			if let Some(value) = value {
+				return value;
+			}
+			core::hint::spin_loop();
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#![allow(clippy::unwrap_used)]
+	use core::{pin::Pin, task::Context};
+
+	use alloc::sync::Arc;
+
+	use crate::port_changed::PortChanged;
+
+	use super::*;
+
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	// check, that the auto traits are available.
+	#[test]
+	const fn normal_types() {
+		is_normal::<&LockFreeCell<f64>>();
+		is_normal::<LockFreeCell<(f64, f64, f64)>>();
+	}
+
+	#[test]
+	fn get_and_set() {
+		let cell = LockFreeCell::<(u64, u64)>::new(None);
+		assert_eq!(cell.sequence_number(), 0);
+		assert!(cell.get().is_none());
+		cell.set(Some((1, 1)));
+		assert_eq!(cell.get(), Some((1, 1)));
+		assert_eq!(cell.sequence_number(), 1);
+
+		let cell = Arc::new(LockFreeCell::new(Some(0_u32)));
+		assert_eq!(cell.sequence_number(), 1);
+		let observed = Arc::new(AtomicU32::new(0));
+		let observer = observed.clone();
+		cell.subscribe(Observer::new(
+			"p1",
+			Arc::new(move |_, sequence_number| observer.store(sequence_number, Ordering::SeqCst)),
+		));
+		cell.set(Some(42));
+		assert_eq!(observed.load(Ordering::SeqCst), 2);
+	}
+
+	#[test]
+	fn notification_hand_off() {
+		let cell = LockFreeCell::new(Some(0_u32));
+		let observed = Arc::new(AtomicU32::new(0));
+		let observer = observed.clone();
+		cell.subscribe(Observer::new(
+			"p1",
+			Arc::new(move |_, sequence_number| observer.store(sequence_number, Ordering::SeqCst)),
+		));
+		// the writer does not wait for locked subscribers, the lock holder delivers the notification
+		let guard = cell.subscribers.write();
+		cell.set(Some(1));
+		cell.set(Some(2));
+		assert_eq!(observed.load(Ordering::SeqCst), 0);
+		drop(guard);
+		cell.deliver();
+		assert_eq!(observed.load(Ordering::SeqCst), 3);
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn concurrent_access() {
+		extern crate std;
+
+		let cell = Arc::new(LockFreeCell::new(Some((0_u64, 0_u64))));
+		let writers: Vec<_> = (0..2)
+			.map(|_| {
+				let cell = cell.clone();
+				std::thread::spawn(move || {
+					for value in 1..=10_000 {
+						cell.set(Some((value, value)));
+					}
+				})
+			})
+			.collect();
+		let readers: Vec<_> = (0..2)
+			.map(|_| {
+				let cell = cell.clone();
+				std::thread::spawn(move || {
+					for _ in 0..10_000 {
+						let (first, second) = cell.get().unwrap();
+						assert_eq!(first, second);
+					}
+				})
+			})
+			.collect();
+		for handle in writers.into_iter().chain(readers) {
+			handle.join().unwrap();
+		}
+		assert_eq!(cell.sequence_number(), 20_001);
+	}
+
+	#[test]
+	fn removed_wakers() {
+		let cell = Arc::new(LockFreeCell::<i32>::new(None));
+		let mut changed = PortChanged::from_cell(cell.clone(), 0);
+		let mut cx = Context::from_waker(Waker::noop());
+		assert!(Pin::new(&mut changed).poll(&mut cx).is_pending());
+		assert!(Pin::new(&mut changed).poll(&mut cx).is_pending());
+		assert_eq!(cell.subscribers.read().wakers.len(), 1);
+		drop(changed);
+		assert_eq!(cell.subscribers.read().wakers.len(), 0);
+	}
+}
@@ -0,0 +1,329 @@
+// Copyright © 2025 Stephan Kunz
+//! Implementation of a lock-free port providing the trait [`InBound`] for `Copy` types.
+
+use core::any::Any;
+
+use alloc::{sync::Arc, vec::Vec};
+use portable_atomic::{AtomicPtr, AtomicUsize, Ordering};
+
+use crate::{
+	ConstString, RwLock,
+	error::{Error, Result},
+	lock_free_cell::LockFreeCell,
+	lock_free_out_port::LockFreeOutBoundPort,
+	port::PortDirection,
+	port_changed::PortChanged,
+	port_value::{Observer, PortValue, PortValuePtr, PortValueReadGuard},
+	traits::{InBound, PortCommons, PortInfo},
+};
+
+/// The binding state of a [`LockFreeInBoundPort`].
+struct Binding<T> {
+	/// The value the port is bound to.
+	cell: Arc<LockFreeCell<T>>,
+	/// The name of the source port, if the port is bound.
+	source: Option<ConstString>,
+	/// The [`Observer`]s registered via this port, they follow the port when (re)binding.
+	observers: Vec<Observer>,
+	/// The cells the port has been bound to before, kept alive for readers still accessing them.
+	/// They are released by the next (re)binding, which sees no reader.
+	retired: Vec<Arc<LockFreeCell<T>>>,
+}
+
+/// The internal state of a [`LockFreeInBoundPort`].
+struct Shared<T> {
+	/// An identifying name of the port.
+	name: ConstString,
+	/// The cell of the [`Binding`], read without taking a lock.
+	current: AtomicPtr<LockFreeCell<T>>,
+	/// The number of readers accessing a cell via `current`.
+	readers: AtomicUsize,
+	/// The empty cell of the unbound port.
+	unbound: Arc<LockFreeCell<T>>,
+	/// The binding, locked for (re)binding only.
+	binding: RwLock<Binding<T>>,
+}
+
+impl<T> Shared<T> {
+	fn new(
+		name: ConstString,
+		cell: Arc<LockFreeCell<T>>,
+		unbound: Arc<LockFreeCell<T>>,
+		source: Option<ConstString>,
+	) -> Self {
+		Self {
+			name,
+			current: AtomicPtr::new(Arc::as_ptr(&cell).cast_mut()),
+			readers: AtomicUsize::new(0),
+			unbound,
+			binding: RwLock::new(Binding {
+				cell,
+				source,
+				observers: Vec::new(),
+				retired: Vec::new(),
+			}),
+		}
+	}
+
+	/// Calls `f` with the current cell.
+	fn with_cell<R>(&self, f: impl FnOnce(&LockFreeCell<T>) -> R) -> R {
+		self.readers.fetch_add(1, Ordering::SeqCst);
+		// SAFETY: the pointer refers to the cell of the binding or to a retired cell,
+		// which are not released while there are readers.
+		#[allow(unsafe_code)]
+		let cell = unsafe { &*self.current.load(Ordering::SeqCst) };
+		let result = f(cell);
+		self.readers.fetch_sub(1, Ordering::SeqCst);
+		result
+	}
+
+	/// Replaces the cell of the `binding`, moving the [`Observer`]s to the new cell.
+	fn set_cell(&self, binding: &mut Binding<T>, cell: Arc<LockFreeCell<T>>) {
+		for observer in &binding.observers {
+			binding.cell.unsubscribe(observer);
+			cell.subscribe(observer.clone());
+		}
+		self.current
+			.store(Arc::as_ptr(&cell).cast_mut(), Ordering::SeqCst);
+		let old = core::mem::replace(&mut binding.cell, cell);
+		if !Arc::ptr_eq(&old, &self.unbound)
+			&& !binding
+				.retired
+				.iter()
+				.any(|retired| Arc::ptr_eq(retired, &old))
+		{
+			binding.retired.push(old);
+		}
+		// readers starting from now on see the new cell, so without readers no retired cell is accessed
+		if self.readers.load(Ordering::SeqCst) == 0 {
+			binding.retired.clear();
+		}
+	}
+}
+
+/// LockFreeInBoundPort
+///
+/// An in-bound port for `Copy` types, whose reads never block the writer of the bound [`LockFreeOutBoundPort`].
+/// Reading the value and its sequence number takes no lock, only (re)binding the port does.
+/// Reading is lock-free, but not wait-free: it retries, if a new value is published meanwhile.
+/// The values the port has been bound to before are kept for concurrent readers,
+/// until a later (re)binding happens without a concurrent read or the port is dropped.
+#[repr(transparent)]
+pub struct LockFreeInBoundPort<T>(Arc<Shared<T>>);
+
+impl<T> Clone for LockFreeInBoundPort<T> {
+	/// A clone does not take over the [`Observer`]s.
+	fn clone(&self) -> Self {
+		let binding = self.0.binding.read();
+		Self(Arc::new(Shared::new(
+			self.0.name.clone(),
+			binding.cell.clone(),
+			self.0.unbound.clone(),
+			binding.source.clone(),
+		)))
+	}
+}
+
+impl<T> core::fmt::Debug for LockFreeInBoundPort<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		let binding = self.0.binding.read();
+		f.debug_struct("LockFreeInBoundPort")
+			.field("name", &self.0.name)
+			.field("source", &binding.source)
+			.field("cell", &binding.cell)
+			.finish()
+	}
+}
+
+impl<T: Any> PortInfo for LockFreeInBoundPort<T> {
+	type Value = T;
+	const DIRECTION: PortDirection = PortDirection::In;
+
+	fn value_address(&self) -> usize {
+		self.0.current.load(Ordering::Acquire).addr()
+	}
+}
+
+impl<T> PortCommons for LockFreeInBoundPort<T> {
+	fn name(&self) -> ConstString {
+		self.0.name.clone()
+	}
+
+	fn sequence_number(&self) -> u32 {
+		self.0.with_cell(LockFreeCell::sequence_number)
+	}
+
+	fn is_bound(&self) -> bool {
+		self.0.binding.read().source.is_some()
+	}
+
+	fn bound_source_name(&self) -> Option<ConstString> {
+		self.0.binding.read().source.clone()
+	}
+}
+
+impl<T: Copy> InBound<T> for LockFreeInBoundPort<T> {
+	fn get(&self) -> Option<T>
+	where
+		T: Clone,
+	{
+		self.0.with_cell(LockFreeCell::get)
+	}
+
+	/// Returns a guard to a copy of the value.
+	fn read(&self) -> Result<PortValueReadGuard<T>> {
+		let (name, value) = self.snapshot()?;
+		PortValueReadGuard::new(name, value)
+	}
+
+	/// Returns a guard to a copy of the value.
+	fn try_read(&self) -> Result<PortValueReadGuard<T>> {
+		let (name, value) = self.snapshot()?;
+		PortValueReadGuard::try_new(name, value)
+	}
+
+	fn on_change(&self, callback: impl Fn(&str, u32) + Send + Sync + 'static) {
+		self.subscribe(callback);
+	}
+
+	fn wait_for_change(&self, since: u32) -> PortChanged<T> {
+		PortChanged::from_cell(self.cell(), since)
+	}
+}
+
+impl<T: Copy> LockFreeInBoundPort<T> {
+	#[must_use]
+	pub fn new(name: impl Into<ConstString>) -> Self {
+		let unbound = Arc::new(LockFreeCell::new(None));
+		Self(Arc::new(Shared::new(name.into(), unbound.clone(), unbound, None)))
+	}
+
+	/// Returns the name of the port together with a detached copy of the value.
+	fn snapshot(&self) -> Result<(ConstString, PortValuePtr<T>)> {
+		self.get().map_or_else(
+			|| {
+				Err(Error::NoValueSet {
+					port: self.0.name.clone(),
+				})
+			},
+			|value| Ok((self.0.name.clone(), PortValuePtr::new(PortValue::new(value)))),
+		)
+	}
+}
+
+impl<T> LockFreeInBoundPort<T> {
+	/// Binds the port to the value of a [`LockFreeOutBoundPort`].
+	/// # Errors
+	/// - [`Error::AlreadyBound`], if the port is already bound.
+	pub fn bind_to_out_port(&mut self, port: &LockFreeOutBoundPort<T>) -> Result<()> {
+		self.bind(port.name(), port.cell())
+	}
+
+	/// Binds the port to the value of a [`LockFreeOutBoundPort`], replacing an existing binding.
+	pub fn rebind_to_out_port(&mut self, port: &LockFreeOutBoundPort<T>) {
+		self.rebind(port.name(), port.cell());
+	}
+
+	/// Removes the binding of the port, leaving it with an empty value.
+	/// # Errors
+	/// - [`Error::NotBound`], if the port is not bound.
+	pub fn unbind(&self) -> Result<()> {
+		let mut binding = self.0.binding.write();
+		if binding.source.take().is_none() {
+			return Err(Error::NotBound {
+				port: self.0.name.clone(),
+			});
+		}
+		self.0
+			.set_cell(&mut binding, self.0.unbound.clone());
+		Ok(())
+	}
+
+	/// Returns a handle sharing the internal state with `self`,
+	/// in contrast to [`Clone`], which creates a detached copy.
+	pub(crate) fn shared(&self) -> Self {
+		Self(self.0.clone())
+	}
+
+	pub(crate) fn bind(&self, source: impl Into<ConstString>, cell: Arc<LockFreeCell<T>>) -> Result<()> {
+		let mut binding = self.0.binding.write();
+		if binding.source.is_some() {
+			return Err(Error::AlreadyBound {
+				port: self.0.name.clone(),
+			});
+		}
+		binding.source = Some(source.into());
+		self.0.set_cell(&mut binding, cell);
+		Ok(())
+	}
+
+	pub(crate) fn rebind(&self, source: impl Into<ConstString>, cell: Arc<LockFreeCell<T>>) {
+		let mut binding = self.0.binding.write();
+		binding.source = Some(source.into());
+		self.0.set_cell(&mut binding, cell);
+	}
+
+	/// Returns the cell the port is bound to.
+	pub(crate) fn cell(&self) -> Arc<LockFreeCell<T>> {
+		self.0.binding.read().cell.clone()
+	}
+
+	/// Registers a `callback` without requiring `T: Copy`, used by the type erased [`Port`](crate::Port).
+	pub(crate) fn subscribe(&self, callback: impl Fn(&str, u32) + Send + Sync + 'static) {
+		let mut binding = self.0.binding.write();
+		let observer = Observer::new(self.0.name.clone(), Arc::new(callback));
+		binding.cell.subscribe(observer.clone());
+		binding.observers.push(observer);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#![allow(clippy::unwrap_used)]
+	use crate::traits::OutBound;
+
+	use super::*;
+
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	// check, that the auto traits are available.
+	#[test]
+	const fn normal_types() {
+		is_normal::<&LockFreeInBoundPort<f64>>();
+		is_normal::<LockFreeInBoundPort<(f64, f64)>>();
+	}
+
+	#[test]
+	fn released_cells() {
+		let i1 = LockFreeInBoundPort::<i32>::new("i1");
+		for value in 0..10 {
+			let o1 = LockFreeOutBoundPort::<i32>::with_value("o1", value);
+			i1.rebind("o1", o1.cell());
+			assert_eq!(i1.get(), Some(value));
+		}
+		// without concurrent readers the replaced cells are released
+		assert!(i1.0.binding.read().retired.is_empty());
+	}
+
+	#[test]
+	fn binding() {
+		let o1 = LockFreeOutBoundPort::<i32>::with_value("o1", 1);
+		let mut i1 = LockFreeInBoundPort::<i32>::new("i1");
+		assert!(i1.get().is_none());
+		assert!(i1.read().is_err());
+		i1.bind_to_out_port(&o1).unwrap();
+		assert!(i1.bind_to_out_port(&o1).is_err());
+		assert_eq!(i1.bound_source_name().unwrap().as_ref(), "o1");
+		assert_eq!(i1.get(), Some(1));
+		o1.set(2);
+		assert_eq!(*i1.read().unwrap(), 2);
+		assert_eq!(*i1.try_read().unwrap(), 2);
+		assert_eq!(i1.sequence_number(), 2);
+
+		i1.unbind().unwrap();
+		assert!(i1.unbind().is_err());
+		assert!(i1.get().is_none());
+		i1.rebind_to_out_port(&o1);
+		assert_eq!(i1.get(), Some(2));
+	}
+}
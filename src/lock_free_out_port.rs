@@ -0,0 +1,178 @@
+// Copyright © 2025 Stephan Kunz
+//! Implementation of a lock-free port providing the trait [`OutBound`] for `Copy` types.
+
+use core::any::Any;
+
+use alloc::{boxed::Box, sync::Arc};
+
+use crate::{
+	ConstString,
+	error::{Error, Result},
+	lock_free_cell::LockFreeCell,
+	port::PortDirection,
+	port_value::{PortValue, PortValuePtr, PortValueWriteGuard},
+	traits::{OutBound, PortCommons, PortInfo},
+};
+
+/// LockFreeOutBoundPort
+///
+/// An out-bound port for `Copy` types, whose value is set without taking a lock.
+/// Setting is lock-free, but not wait-free: it retries while all spare buffers of the value are read or written by others.
+/// Subscribers locked by another thread are notified by that thread.
+/// It can be bound to [`LockFreeInBoundPort`](crate::LockFreeInBoundPort)s.
+pub struct LockFreeOutBoundPort<T> {
+	/// An identifying name of the port.
+	name: ConstString,
+	/// The value.
+	cell: Arc<LockFreeCell<T>>,
+}
+
+impl<T> Clone for LockFreeOutBoundPort<T> {
+	fn clone(&self) -> Self {
+		Self {
+			name: self.name.clone(),
+			cell: self.cell.clone(),
+		}
+	}
+}
+
+impl<T> core::fmt::Debug for LockFreeOutBoundPort<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("LockFreeOutBoundPort")
+			.field("name", &self.name)
+			.field("cell", &self.cell)
+			.finish()
+	}
+}
+
+impl<T: Any> PortInfo for LockFreeOutBoundPort<T> {
+	type Value = T;
+	const DIRECTION: PortDirection = PortDirection::Out;
+
+	fn value_address(&self) -> usize {
+		Arc::as_ptr(&self.cell).addr()
+	}
+}
+
+impl<T> PortCommons for LockFreeOutBoundPort<T> {
+	fn name(&self) -> ConstString {
+		self.name.clone()
+	}
+
+	fn sequence_number(&self) -> u32 {
+		self.cell.sequence_number()
+	}
+
+	fn is_bound(&self) -> bool {
+		false
+	}
+
+	fn bound_source_name(&self) -> Option<ConstString> {
+		None
+	}
+}
+
+impl<T: Copy + Send + Sync + 'static> OutBound<T> for LockFreeOutBoundPort<T> {
+	fn set(&self, value: impl Into<T>) {
+		self.cell.set(Some(value.into()));
+	}
+
+	/// Returns a guard to a copy of the value, a modified copy is written back when the guard is dropped.
+	/// Changes done by others in between are overwritten.
+	fn write(&self) -> Result<PortValueWriteGuard<T>> {
+		PortValueWriteGuard::new(self.name.clone(), write_back(&self.name, &self.cell)?)
+	}
+
+	/// Returns a guard to a copy of the value, a modified copy is written back when the guard is dropped.
+	/// Changes done by others in between are overwritten.
+	fn try_write(&self) -> Result<PortValueWriteGuard<T>> {
+		PortValueWriteGuard::try_new(self.name.clone(), write_back(&self.name, &self.cell)?)
+	}
+}
+
+impl<T: Copy> LockFreeOutBoundPort<T> {
+	#[must_use]
+	pub fn new(name: impl Into<ConstString>) -> Self {
+		Self {
+			name: name.into(),
+			cell: Arc::new(LockFreeCell::new(None)),
+		}
+	}
+
+	#[must_use]
+	pub fn with_value(name: impl Into<ConstString>, value: impl Into<T>) -> Self {
+		Self {
+			name: name.into(),
+			cell: Arc::new(LockFreeCell::new(Some(value.into()))),
+		}
+	}
+
+	/// Returns a copy of the current value.
+	#[must_use]
+	pub fn get(&self) -> Option<T> {
+		self.cell.get()
+	}
+}
+
+impl<T> LockFreeOutBoundPort<T> {
+	/// Returns a handle sharing the internal state with `self`.
+	pub(crate) fn shared(&self) -> Self {
+		self.clone()
+	}
+
+	pub(crate) fn cell(&self) -> Arc<LockFreeCell<T>> {
+		self.cell.clone()
+	}
+}
+
+/// Creates a detached copy of the value in `cell`, which is written back into `cell` on each change.
+/// # Errors
+/// - [`Error::NoValueSet`] if the cell does not contain a value.
+pub(crate) fn write_back<T: Copy + Send + Sync + 'static>(
+	port: &ConstString,
+	cell: &Arc<LockFreeCell<T>>,
+) -> Result<PortValuePtr<T>> {
+	let value = cell
+		.get()
+		.ok_or_else(|| Error::NoValueSet { port: port.clone() })?;
+	let copy = PortValuePtr::new(PortValue::new(value));
+	let cell = cell.clone();
+	copy.set_recorder(Box::new(move |_, value: &T| cell.set(Some(*value))));
+	Ok(copy)
+}
+
+#[cfg(test)]
+mod tests {
+	#![allow(clippy::unwrap_used)]
+	use super::*;
+
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	// check, that the auto traits are available.
+	#[test]
+	const fn normal_types() {
+		is_normal::<&LockFreeOutBoundPort<f64>>();
+		is_normal::<LockFreeOutBoundPort<(f64, f64)>>();
+	}
+
+	#[test]
+	fn accessors() {
+		let o1 = LockFreeOutBoundPort::<f64>::new("o1");
+		assert_eq!(o1.sequence_number(), 0);
+		assert!(o1.write().is_err());
+		o1.set(1.0);
+		assert_eq!(o1.get(), Some(1.0));
+		{
+			let mut guard = o1.write().unwrap();
+			*guard += 1.0;
+			// the copy is written back on drop
+			assert_eq!(o1.get(), Some(1.0));
+		}
+		assert_eq!(o1.get(), Some(2.0));
+		assert_eq!(o1.sequence_number(), 2);
+		{
+			let _guard = o1.try_write().unwrap();
+		}
+		assert_eq!(o1.sequence_number(), 2);
+	}
+}
@@ -0,0 +1,244 @@
+// Copyright © 2025 Stephan Kunz
+//! Implementation of an outgoing port keeping a history of its values.
+
+use core::any::Any;
+
+use alloc::{boxed::Box, collections::vec_deque::VecDeque, sync::Arc, vec::Vec};
+
+use crate::{
+	ConstString, RwLock,
+	error::Result,
+	port::PortDirection,
+	port_data::PortData,
+	port_value::{PortValuePtr, PortValueWriteGuard},
+	sequence_number::SequenceNumber,
+	traits::{OutBound, PortCommons, PortInfo},
+};
+
+/// Ring buffer of the last values together with their sequence numbers.
+struct History<T> {
+	/// The recorded values, oldest first.
+	entries: VecDeque<(u32, T)>,
+	/// Maximum number of recorded values.
+	capacity: usize,
+}
+
+impl<T> History<T> {
+	fn record(&mut self, sequence_number: u32, value: T) {
+		if self.entries.len() >= self.capacity {
+			self.entries.pop_front();
+		}
+		self.entries.push_back((sequence_number, value));
+	}
+}
+
+/// OutBoundHistoryPort
+///
+/// Behaves like an [`OutBoundPort`](crate::OutBoundPort) and can be bound in the same way,
+/// so bound ports see the latest value.
+/// Additionally it keeps the last values together with their sequence numbers.
+/// Each change of the value is recorded, regardless of whether it is done via this port or a bound port.
+pub struct OutBoundHistoryPort<T>(Arc<RwLock<PortData<T>>>, Arc<RwLock<History<T>>>);
+
+impl<T> Clone for OutBoundHistoryPort<T> {
+	fn clone(&self) -> Self {
+		Self(Arc::new(RwLock::new((*self.0.read()).clone())), self.1.clone())
+	}
+}
+
+impl<T> core::fmt::Debug for OutBoundHistoryPort<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_tuple("OutBoundHistoryPort")
+			.field(&self.0)
+			.finish_non_exhaustive()
+	}
+}
+
+impl<T: Any> PortInfo for OutBoundHistoryPort<T> {
+	type Value = T;
+	const DIRECTION: PortDirection = PortDirection::Out;
+
+	fn value_address(&self) -> usize {
+		self.value().address()
+	}
+}
+
+impl<T> PortCommons for OutBoundHistoryPort<T> {
+	fn name(&self) -> ConstString {
+		self.0.read().name()
+	}
+
+	fn sequence_number(&self) -> u32 {
+		self.0.read().sequence_number()
+	}
+
+	fn is_bound(&self) -> bool {
+		self.0.read().is_bound()
+	}
+
+	fn bound_source_name(&self) -> Option<ConstString> {
+		self.0.read().bound_source_name()
+	}
+}
+
+impl<T> OutBound<T> for OutBoundHistoryPort<T> {
+	fn set(&self, value: impl Into<T>) {
+		self.value().set(value);
+	}
+
+	fn write(&self) -> Result<PortValueWriteGuard<T>> {
+		self.0.read().write()
+	}
+
+	fn try_write(&self) -> Result<PortValueWriteGuard<T>> {
+		self.0.read().try_write()
+	}
+}
+
+impl<T: Any + Clone + Send + Sync> OutBoundHistoryPort<T> {
+	/// Creates a port keeping the last `capacity` values, a `capacity` of `0` is treated as `1`.
+	#[must_use]
+	pub fn new(name: impl Into<ConstString>, capacity: usize) -> Self {
+		let capacity = capacity.max(1);
+		let history = Arc::new(RwLock::new(History {
+			entries: VecDeque::with_capacity(capacity),
+			capacity,
+		}));
+		let data = PortData::new(name.into());
+		let recorder = history.clone();
+		data.value()
+			.set_recorder(Box::new(move |sequence_number, value: &T| {
+				recorder
+					.write()
+					.record(sequence_number, value.clone());
+			}));
+		Self(Arc::new(RwLock::new(data)), history)
+	}
+
+	/// Creates a port keeping the last `capacity` values with an initial `value`.
+	#[must_use]
+	pub fn with_value(name: impl Into<ConstString>, capacity: usize, value: impl Into<T>) -> Self {
+		let port = Self::new(name, capacity);
+		port.set(value);
+		port
+	}
+}
+
+impl<T> OutBoundHistoryPort<T> {
+	/// Returns the recorded values with their sequence numbers, oldest first.
+	pub fn history(&self) -> impl Iterator<Item = (u32, T)>
+	where
+		T: Clone,
+	{
+		self.1
+			.read()
+			.entries
+			.iter()
+			.cloned()
+			.collect::<Vec<_>>()
+			.into_iter()
+	}
+
+	/// Returns the latest recorded value with its sequence number.
+	#[must_use]
+	pub fn latest(&self) -> Option<(u32, T)>
+	where
+		T: Clone,
+	{
+		self.1.read().entries.back().cloned()
+	}
+
+	/// Returns the recorded values changed after the one with `sequence_number`, oldest first.
+	/// A wrap around of the sequence number is taken into account.
+	pub fn since(&self, sequence_number: u32) -> impl Iterator<Item = (u32, T)>
+	where
+		T: Clone,
+	{
+		self.1
+			.read()
+			.entries
+			.iter()
+			.filter(|(recorded, _)| SequenceNumber::is_after(*recorded, sequence_number))
+			.cloned()
+			.collect::<Vec<_>>()
+			.into_iter()
+	}
+
+	/// Returns the maximum number of recorded values.
+	#[must_use]
+	pub fn capacity(&self) -> usize {
+		self.1.read().capacity
+	}
+
+	/// Returns a handle sharing the internal state with `self`,
+	/// in contrast to [`Clone`], which creates a detached copy.
+	pub(crate) fn shared(&self) -> Self {
+		Self(self.0.clone(), self.1.clone())
+	}
+
+	pub(crate) fn value(&self) -> PortValuePtr<T> {
+		self.0.read().value()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#![allow(clippy::unwrap_used)]
+	use alloc::{string::String, vec};
+
+	use super::*;
+
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	// check, that the auto traits are available.
+	#[test]
+	const fn normal_types() {
+		is_normal::<&OutBoundHistoryPort<i32>>();
+		is_normal::<OutBoundHistoryPort<String>>();
+	}
+
+	#[test]
+	fn history() {
+		let o1 = OutBoundHistoryPort::<i32>::new("o1", 3);
+		assert_eq!(o1.capacity(), 3);
+		assert!(o1.latest().is_none());
+		for value in 1..=4 {
+			o1.set(value * 10);
+		}
+		assert_eq!(o1.sequence_number(), 4);
+		assert_eq!(o1.history().collect::<Vec<_>>(), vec![(2, 20), (3, 30), (4, 40)]);
+		assert_eq!(o1.latest(), Some((4, 40)));
+		assert_eq!(o1.since(3).collect::<Vec<_>>(), vec![(4, 40)]);
+		assert_eq!(o1.since(4).count(), 0);
+
+		{
+			let mut guard = o1.write().unwrap();
+			*guard += 1;
+		}
+		assert_eq!(o1.latest(), Some((5, 41)));
+		{
+			let _guard = o1.write().unwrap();
+		}
+		assert_eq!(o1.latest(), Some((5, 41)));
+
+		let o2 = OutBoundHistoryPort::<i32>::with_value("o2", 0, 1);
+		assert_eq!(o2.capacity(), 1);
+		o2.set(2);
+		assert_eq!(o2.history().collect::<Vec<_>>(), vec![(2, 2)]);
+
+		// wrap around of the sequence number
+		let o3 = OutBoundHistoryPort::<i32>::new("o3", 3);
+		o3.0.read()
+			.value()
+			.write()
+			.mirror(None, u32::MAX - 1);
+		for value in 1..=3 {
+			o3.set(value);
+		}
+		assert_eq!(o3.history().collect::<Vec<_>>(), vec![(u32::MAX, 1), (1, 2), (2, 3)]);
+		assert_eq!(o3.since(u32::MAX - 1).count(), 3);
+		assert_eq!(o3.since(u32::MAX).collect::<Vec<_>>(), vec![(1, 2), (2, 3)]);
+		assert_eq!(o3.since(1).collect::<Vec<_>>(), vec![(2, 3)]);
+		assert_eq!(o3.since(0).count(), 3);
+	}
+}
@@ -10,18 +10,19 @@ use alloc::sync::Arc;
 use crate::{
 	ConstString, RwLock,
 	error::{Error, Result},
+	port::PortDirection,
 	port_data::PortData,
 	port_value::{PortValuePtr, PortValueReadGuard, PortValueWriteGuard},
-	traits::{AnyPort, OutBound, PortCommons},
+	traits::{AnyPort, OutBound, PortCommons, PortInfo},
 };
 
 /// OutBoundPort
 #[repr(transparent)]
-pub struct OutBoundPort<T>(RwLock<PortData<T>>);
+pub struct OutBoundPort<T>(Arc<RwLock<PortData<T>>>);
 
 impl<T> Clone for OutBoundPort<T> {
 	fn clone(&self) -> Self {
-		Self(RwLock::new((*self.0.read()).clone()))
+		Self(Arc::new(RwLock::new((*self.0.read()).clone())))
 	}
 }
 
@@ -48,6 +49,15 @@ impl<T: 'static> PartialEq for OutBoundPort<T> {
 	}
 }
 
+impl<T: Any> PortInfo for OutBoundPort<T> {
+	type Value = T;
+	const DIRECTION: PortDirection = PortDirection::Out;
+
+	fn value_address(&self) -> usize {
+		self.value().address()
+	}
+}
+
 impl<T> PortCommons for OutBoundPort<T> {
 	fn name(&self) -> ConstString {
 		self.0.read().name()
@@ -56,11 +66,19 @@ impl<T> PortCommons for OutBoundPort<T> {
 	fn sequence_number(&self) -> u32 {
 		self.0.read().sequence_number()
 	}
+
+	fn is_bound(&self) -> bool {
+		self.0.read().is_bound()
+	}
+
+	fn bound_source_name(&self) -> Option<ConstString> {
+		self.0.read().bound_source_name()
+	}
 }
 
 impl<T> OutBound<T> for OutBoundPort<T> {
 	fn set(&self, value: impl Into<T>) {
-		self.0.read().value().write().set(value.into())
+		self.value().set(value)
 	}
 
 	fn write(&self) -> Result<PortValueWriteGuard<T>> {
@@ -97,12 +115,12 @@ impl<T> OutBound<T> for OutBoundPort<T> {
 impl<T> OutBoundPort<T> {
 	#[must_use]
 	pub fn new(name: impl Into<ConstString>) -> Self {
-		Self(RwLock::new(PortData::new(name.into())))
+		Self(Arc::new(RwLock::new(PortData::new(name.into()))))
 	}
 
 	#[must_use]
 	pub fn with_value(name: impl Into<ConstString>, value: impl Into<T>) -> Self {
-		Self(RwLock::new(PortData::with_value(name.into(), value.into())))
+		Self(Arc::new(RwLock::new(PortData::with_value(name.into(), value.into()))))
 	}
 
 	/// Helper function to solve ambiguity.
@@ -147,7 +165,13 @@ impl<T> OutBoundPort<T> {
 
 	#[must_use]
 	pub(crate) fn by_value(&self) -> Option<T> {
-		self.0.read().value().write().take()
+		self.value().take()
+	}
+
+	/// Returns a handle sharing the internal state with `self`,
+	/// in contrast to [`Clone`], which creates a detached copy.
+	pub(crate) fn shared(&self) -> Self {
+		Self(self.0.clone())
 	}
 
 	pub(crate) fn value(&self) -> PortValuePtr<T> {
@@ -0,0 +1,159 @@
+// Copyright © 2025 Stephan Kunz
+//! Implementation of an outgoing queue port.
+
+use core::any::Any;
+
+use alloc::sync::Arc;
+
+use crate::{
+	ConstString, RwLock,
+	error::Result,
+	port::PortDirection,
+	port_data::PortData,
+	port_queue::{self, OverflowPolicy, Queue},
+	port_value::PortValuePtr,
+	traits::{PortCommons, PortInfo},
+};
+
+/// OutBoundQueuePort
+///
+/// Writing pushes values into a bounded FIFO queue, which is shared with the bound [`InBoundQueuePort`](crate::InBoundQueuePort)s.
+#[repr(transparent)]
+pub struct OutBoundQueuePort<T>(Arc<RwLock<PortData<Queue<T>>>>);
+
+impl<T> Clone for OutBoundQueuePort<T> {
+	fn clone(&self) -> Self {
+		Self(Arc::new(RwLock::new((*self.0.read()).clone())))
+	}
+}
+
+impl<T> core::fmt::Debug for OutBoundQueuePort<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_tuple("OutBoundQueuePort")
+			.field(&self.0)
+			.finish()
+	}
+}
+
+impl<T: Any> PortInfo for OutBoundQueuePort<T> {
+	type Value = T;
+	const DIRECTION: PortDirection = PortDirection::Out;
+
+	fn value_address(&self) -> usize {
+		self.value().address()
+	}
+}
+
+impl<T> PortCommons for OutBoundQueuePort<T> {
+	fn name(&self) -> ConstString {
+		self.0.read().name()
+	}
+
+	fn sequence_number(&self) -> u32 {
+		self.0.read().sequence_number()
+	}
+
+	fn is_bound(&self) -> bool {
+		self.0.read().is_bound()
+	}
+
+	fn bound_source_name(&self) -> Option<ConstString> {
+		self.0.read().bound_source_name()
+	}
+}
+
+impl<T> OutBoundQueuePort<T> {
+	/// Creates a port with an empty queue of the given `capacity` and overflow `policy`.
+	/// A `capacity` of `0` is treated as `1`.
+	#[must_use]
+	pub fn new(name: impl Into<ConstString>, capacity: usize, policy: OverflowPolicy) -> Self {
+		Self(Arc::new(RwLock::new(PortData::with_initial_value(
+			name.into(),
+			Queue::new(capacity, policy),
+		))))
+	}
+
+	/// Pushes a value into the queue.
+	/// Each stored value increments the sequence number and is notified to the observers.
+	/// # Errors
+	/// - [`Error::QueueFull`](crate::Error::QueueFull), if the queue is full and the policy is [`OverflowPolicy::Error`].
+	pub fn push(&self, value: impl Into<T>) -> Result<()> {
+		port_queue::push(self.name(), &self.value(), value.into())
+	}
+
+	/// Returns the number of values in the queue.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		port_queue::len(&self.value())
+	}
+
+	/// Returns `true` if the queue is empty.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Returns the capacity of the queue.
+	#[must_use]
+	pub fn capacity(&self) -> usize {
+		self.value()
+			.read()
+			.as_ref()
+			.map_or(0, Queue::capacity)
+	}
+
+	/// Returns the overflow policy of the queue.
+	#[must_use]
+	pub fn policy(&self) -> OverflowPolicy {
+		self.value()
+			.read()
+			.as_ref()
+			.map_or_else(OverflowPolicy::default, Queue::policy)
+	}
+
+	/// Returns a handle sharing the internal state with `self`,
+	/// in contrast to [`Clone`], which creates a detached copy.
+	pub(crate) fn shared(&self) -> Self {
+		Self(self.0.clone())
+	}
+
+	pub(crate) fn value(&self) -> PortValuePtr<Queue<T>> {
+		self.0.read().value()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#![allow(clippy::unwrap_used)]
+	use alloc::string::String;
+
+	use super::*;
+
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	// check, that the auto traits are available.
+	#[test]
+	const fn normal_types() {
+		is_normal::<&OutBoundQueuePort<i32>>();
+		is_normal::<OutBoundQueuePort<String>>();
+	}
+
+	#[test]
+	fn push() {
+		let o1 = OutBoundQueuePort::<i32>::new("o1", 2, OverflowPolicy::Error);
+		assert_eq!(o1.sequence_number(), 0);
+		assert!(o1.is_empty());
+		assert_eq!(o1.capacity(), 2);
+		assert_eq!(o1.policy(), OverflowPolicy::Error);
+		o1.push(1).unwrap();
+		o1.push(2).unwrap();
+		assert!(o1.push(3).is_err());
+		assert_eq!(o1.len(), 2);
+		assert_eq!(o1.sequence_number(), 2);
+
+		let o2 = o1.clone();
+		assert_eq!(o2.len(), 2);
+		assert_eq!(o2.sequence_number(), 2);
+		assert!(o2.push(3).is_err());
+	}
+}
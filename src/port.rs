@@ -1,19 +1,64 @@
 // Copyright © 2025 Stephan Kunz
 //! A type erased (abstract) port implementation.
 
-use core::any::Any;
+use core::any::{Any, TypeId};
 
 use alloc::sync::Arc;
 
 use crate::{
 	ConstString,
+	computed_port::ComputedPort,
+	error::{Error, Result},
 	in_out_port::InOutBoundPort,
 	in_port::InBoundPort,
+	in_queue_port::InBoundQueuePort,
+	lock_free_cell::LockFreeCell,
+	lock_free_in_port::LockFreeInBoundPort,
+	lock_free_out_port::LockFreeOutBoundPort,
+	out_history_port::OutBoundHistoryPort,
 	out_port::OutBoundPort,
-	port_value::PortValuePtr,
-	traits::{AnyPort, PortCommons},
+	out_queue_port::OutBoundQueuePort,
+	port_conversion::{ConvertFn, conversion},
+	port_queue::{OverflowPolicy, Queue},
+	port_value::{DerivedValue, PortValuePtr},
+	traits::{AnyPort, InBound, PortCommons},
 };
 
+/// The direction of the data flow through a port.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PortDirection {
+	/// The port consumes values.
+	In,
+	/// The port produces values.
+	Out,
+	/// The port consumes and produces values.
+	InOut,
+}
+
+impl core::fmt::Display for PortDirection {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			Self::In => write!(f, "in"),
+			Self::Out => write!(f, "out"),
+			Self::InOut => write!(f, "inout"),
+		}
+	}
+}
+
+impl PortDirection {
+	/// Returns `true` if the port consumes values.
+	#[must_use]
+	pub const fn is_input(self) -> bool {
+		matches!(self, Self::In | Self::InOut)
+	}
+
+	/// Returns `true` if the port produces values.
+	#[must_use]
+	pub const fn is_output(self) -> bool {
+		matches!(self, Self::Out | Self::InOut)
+	}
+}
+
 /// Port.
 #[derive(Clone)]
 #[repr(transparent)]
@@ -45,6 +90,105 @@ impl<T: Any + Send + Sync> From<OutBoundPort<T>> for Port {
 	}
 }
 
+impl<T: Any + Send + Sync> From<&InOutBoundPort<T>> for Port {
+	/// The created [`Port`] shares its state with the referenced port.
+	fn from(value: &InOutBoundPort<T>) -> Self {
+		Self(Arc::new(value.shared()))
+	}
+}
+
+impl<T: Any + Send + Sync> From<&InBoundPort<T>> for Port {
+	/// The created [`Port`] shares its state with the referenced port.
+	fn from(value: &InBoundPort<T>) -> Self {
+		Self(Arc::new(value.shared()))
+	}
+}
+
+impl<T: Any + Send + Sync> From<&OutBoundPort<T>> for Port {
+	/// The created [`Port`] shares its state with the referenced port.
+	fn from(value: &OutBoundPort<T>) -> Self {
+		Self(Arc::new(value.shared()))
+	}
+}
+
+impl<T: Any + Send + Sync> From<ComputedPort<T>> for Port {
+	fn from(value: ComputedPort<T>) -> Self {
+		Self(Arc::new(value))
+	}
+}
+
+impl<T: Any + Send + Sync> From<&ComputedPort<T>> for Port {
+	/// The created [`Port`] shares its state with the referenced port.
+	fn from(value: &ComputedPort<T>) -> Self {
+		Self(Arc::new(value.shared()))
+	}
+}
+
+impl<T: Any + Send + Sync> From<InBoundQueuePort<T>> for Port {
+	fn from(value: InBoundQueuePort<T>) -> Self {
+		Self(Arc::new(value))
+	}
+}
+
+impl<T: Any + Send + Sync> From<OutBoundQueuePort<T>> for Port {
+	fn from(value: OutBoundQueuePort<T>) -> Self {
+		Self(Arc::new(value))
+	}
+}
+
+impl<T: Any + Send + Sync> From<&InBoundQueuePort<T>> for Port {
+	/// The created [`Port`] shares its state with the referenced port.
+	fn from(value: &InBoundQueuePort<T>) -> Self {
+		Self(Arc::new(value.shared()))
+	}
+}
+
+impl<T: Any + Send + Sync> From<&OutBoundQueuePort<T>> for Port {
+	/// The created [`Port`] shares its state with the referenced port.
+	fn from(value: &OutBoundQueuePort<T>) -> Self {
+		Self(Arc::new(value.shared()))
+	}
+}
+
+impl<T: Any + Send + Sync> From<OutBoundHistoryPort<T>> for Port {
+	fn from(value: OutBoundHistoryPort<T>) -> Self {
+		Self(Arc::new(value))
+	}
+}
+
+impl<T: Any + Send + Sync> From<&OutBoundHistoryPort<T>> for Port {
+	/// The created [`Port`] shares its state with the referenced port.
+	fn from(value: &OutBoundHistoryPort<T>) -> Self {
+		Self(Arc::new(value.shared()))
+	}
+}
+
+impl<T: Any + Send + Sync> From<LockFreeInBoundPort<T>> for Port {
+	fn from(value: LockFreeInBoundPort<T>) -> Self {
+		Self(Arc::new(value))
+	}
+}
+
+impl<T: Any + Send + Sync> From<LockFreeOutBoundPort<T>> for Port {
+	fn from(value: LockFreeOutBoundPort<T>) -> Self {
+		Self(Arc::new(value))
+	}
+}
+
+impl<T: Any + Send + Sync> From<&LockFreeInBoundPort<T>> for Port {
+	/// The created [`Port`] shares its state with the referenced port.
+	fn from(value: &LockFreeInBoundPort<T>) -> Self {
+		Self(Arc::new(value.shared()))
+	}
+}
+
+impl<T: Any + Send + Sync> From<&LockFreeOutBoundPort<T>> for Port {
+	/// The created [`Port`] shares its state with the referenced port.
+	fn from(value: &LockFreeOutBoundPort<T>) -> Self {
+		Self(Arc::new(value.shared()))
+	}
+}
+
 impl PartialEq for Port {
 	/// Ports are partial equal, if their name, port type & data type are equal.
 	fn eq(&self, other: &Self) -> bool {
@@ -66,6 +210,22 @@ impl PortCommons for Port {
 	fn sequence_number(&self) -> u32 {
 		self.0.sequence_number()
 	}
+
+	fn is_bound(&self) -> bool {
+		self.0.is_bound()
+	}
+
+	fn bound_source_name(&self) -> Option<ConstString> {
+		self.0.bound_source_name()
+	}
+
+	fn is_required(&self) -> bool {
+		self.0.is_required()
+	}
+
+	fn has_default(&self) -> bool {
+		self.0.has_default()
+	}
 }
 
 // helper function to downcast the `Arc<dyn Any>` to `Arc<InPort<T>>`
@@ -88,6 +248,14 @@ impl Port {
 		Self(Arc::new(InBoundPort::<T>::new(name)))
 	}
 
+	/// Creates an in port with a `default` value, see [`InBoundPort::with_default`].
+	pub fn create_in_port_with_default<T: Any + Clone + Send + Sync>(
+		name: impl Into<ConstString>,
+		default: impl Into<T>,
+	) -> Self {
+		Self(Arc::new(InBoundPort::<T>::with_default(name, default)))
+	}
+
 	pub fn create_inout_port<T: Any + Send + Sync>(name: impl Into<ConstString>) -> Self {
 		Self(Arc::new(InOutBoundPort::<T>::new(name)))
 	}
@@ -96,10 +264,197 @@ impl Port {
 		Self(Arc::new(OutBoundPort::<T>::new(name)))
 	}
 
+	pub fn create_out_history_port<T: Any + Clone + Send + Sync>(name: impl Into<ConstString>, capacity: usize) -> Self {
+		Self(Arc::new(OutBoundHistoryPort::<T>::new(name, capacity)))
+	}
+
+	pub fn create_in_queue_port<T: Any + Send + Sync>(name: impl Into<ConstString>) -> Self {
+		Self(Arc::new(InBoundQueuePort::<T>::new(name)))
+	}
+
+	pub fn create_out_queue_port<T: Any + Send + Sync>(
+		name: impl Into<ConstString>,
+		capacity: usize,
+		policy: OverflowPolicy,
+	) -> Self {
+		Self(Arc::new(OutBoundQueuePort::<T>::new(name, capacity, policy)))
+	}
+
+	pub fn create_lock_free_in_port<T: Any + Copy + Send + Sync>(name: impl Into<ConstString>) -> Self {
+		Self(Arc::new(LockFreeInBoundPort::<T>::new(name)))
+	}
+
+	pub fn create_lock_free_out_port<T: Any + Copy + Send + Sync>(name: impl Into<ConstString>) -> Self {
+		Self(Arc::new(LockFreeOutBoundPort::<T>::new(name)))
+	}
+
+	/// Returns the name of the type of the port's value.
+	#[must_use]
+	pub fn type_name(&self) -> &'static str {
+		self.0.value_type_name()
+	}
+
+	/// Returns the [`TypeId`] of the type of the port's value.
+	#[must_use]
+	pub fn value_type_id(&self) -> TypeId {
+		self.0.value_type_id()
+	}
+
+	/// Returns the direction of the port.
+	#[must_use]
+	pub fn direction(&self) -> PortDirection {
+		self.0.direction()
+	}
+
+	/// Returns the address of the port's value, ports sharing their value have the same address.
+	pub(crate) fn value_address(&self) -> usize {
+		self.0.value_address()
+	}
+
+	/// Returns an [`Error::WrongType`] for this port, when a value of type `T` was wanted.
+	pub(crate) fn wrong_type<T: Any>(&self) -> Error {
+		Error::WrongType {
+			port: self.name(),
+			expected: core::any::type_name::<T>(),
+			actual: self.type_name(),
+		}
+	}
+
 	pub(crate) fn port(&self) -> &dyn Any {
 		&*self.0
 	}
 
+	/// Binds the port to the value of the `source` port.
+	/// With `rebind` set, an existing binding is replaced.
+	/// # Errors
+	/// - [`Error::AlreadyBound`], if the port is already bound and `rebind` is not set.
+	/// - [`Error::WrongType`], if one of the ports is not the needed port type & type of T.
+	pub(crate) fn bind<T: Any + Send + Sync>(&self, source: &Self, rebind: bool) -> Result<()> {
+		// a computed value is recomputed on access, so only in ports can be bound to it
+		if let Some(computed) = source.port().downcast_ref::<ComputedPort<T>>() {
+			if let Some(port) = self.port().downcast_ref::<InBoundPort<T>>() {
+				port.bind_derived(source.name(), computed.derived(), rebind)
+			} else {
+				Err(self.wrong_type::<T>())
+			}
+		// source must provide an output value of the wanted type
+		} else if let Some(value) = source.as_out_value::<T>() {
+			// self must want an input value of the wanted type
+			if let Some(port) = self.port().downcast_ref::<InBoundPort<T>>() {
+				if rebind {
+					port.rebind(source.name(), value);
+					Ok(())
+				} else {
+					port.bind(source.name(), value)
+				}
+			} else if let Some(port) = self.port().downcast_ref::<InOutBoundPort<T>>() {
+				if rebind {
+					port.rebind(source.name(), value);
+					Ok(())
+				} else {
+					port.bind(source.name(), value)
+				}
+			} else {
+				Err(self.wrong_type::<T>())
+			}
+		} else if let Some(queue) = source.as_out_queue::<T>() {
+			// self must be a queue port of the wanted type
+			if let Some(port) = self.port().downcast_ref::<InBoundQueuePort<T>>() {
+				if rebind {
+					port.rebind(source.name(), queue);
+					Ok(())
+				} else {
+					port.bind(source.name(), queue)
+				}
+			} else {
+				Err(self.wrong_type::<T>())
+			}
+		} else if let Some(cell) = source.as_lock_free_cell::<T>() {
+			// self must be a lock-free port of the wanted type
+			if let Some(port) = self
+				.port()
+				.downcast_ref::<LockFreeInBoundPort<T>>()
+			{
+				if rebind {
+					port.rebind(source.name(), cell);
+					Ok(())
+				} else {
+					port.bind(source.name(), cell)
+				}
+			} else {
+				Err(self.wrong_type::<T>())
+			}
+		} else {
+			Err(source.wrong_type::<T>())
+		}
+	}
+
+	/// Binds the port to the value of type `S` of the `source` port, converting it with `convert` into `D`.
+	/// # Errors
+	/// - [`Error::AlreadyBound`], if the port is already bound and `rebind` is `false`.
+	/// - [`Error::WrongType`], if the port is not an in port of type `D` or the source not an out port of type `S`.
+	pub(crate) fn bind_with<S: Any + Send + Sync, D: Any + Send + Sync>(
+		&self,
+		source: &Self,
+		convert: ConvertFn<S, D>,
+		rebind: bool,
+	) -> Result<()> {
+		// source must provide an output value of the wanted type, a computed value is recomputed on access
+		let value: Arc<dyn DerivedValue<S>> = if let Some(computed) = source.port().downcast_ref::<ComputedPort<S>>() {
+			computed.derived()
+		} else if let Some(value) = source.as_out_value::<S>() {
+			Arc::new(value)
+		} else {
+			return Err(source.wrong_type::<S>());
+		};
+		// self must want an input value of the wanted type
+		if let Some(port) = self.port().downcast_ref::<InBoundPort<D>>() {
+			port.bind_converted(source.name(), value, convert, rebind)
+		} else {
+			Err(self.wrong_type::<D>())
+		}
+	}
+
+	/// Binds the port to the value of type `S` of the `source` port, converting it into `D`
+	/// with the registered or builtin conversion, see [`Port::register_conversion`].
+	/// # Errors
+	/// - [`Error::AlreadyBound`], if the port is already bound and `rebind` is `false`.
+	/// - [`Error::NoConversion`], if there is no conversion from `S` into `D`.
+	/// - [`Error::WrongType`], if the port is not an in port of type `D` or the source not an out port of type `S`.
+	pub(crate) fn bind_converted<S: Any + Send + Sync, D: Any + Send + Sync>(
+		&self,
+		source: &Self,
+		rebind: bool,
+	) -> Result<()> {
+		let convert = conversion::<S, D>().ok_or_else(|| Error::NoConversion {
+			port: self.name(),
+			from: core::any::type_name::<S>(),
+			to: core::any::type_name::<D>(),
+		})?;
+		self.bind_with(source, convert, rebind)
+	}
+
+	/// Removes the binding of the port.
+	/// # Errors
+	/// - [`Error::NotBound`], if the port is not bound.
+	/// - [`Error::WrongType`], if the port is not the needed port type & type of T.
+	pub(crate) fn unbind<T: Any + Send + Sync>(&self) -> Result<()> {
+		if let Some(port) = self.port().downcast_ref::<InBoundPort<T>>() {
+			port.unbind()
+		} else if let Some(port) = self.port().downcast_ref::<InOutBoundPort<T>>() {
+			port.unbind()
+		} else if let Some(port) = self.port().downcast_ref::<InBoundQueuePort<T>>() {
+			port.unbind()
+		} else if let Some(port) = self
+			.port()
+			.downcast_ref::<LockFreeInBoundPort<T>>()
+		{
+			port.unbind()
+		} else {
+			Err(self.wrong_type::<T>())
+		}
+	}
+
 	pub(crate) fn as_in_value<T: Any + Send + Sync>(&self) -> Option<PortValuePtr<T>> {
 		let in_port = cast_to_in_port::<T>(self.0.clone());
 		if let Some(port) = in_port {
@@ -129,6 +484,17 @@ impl Port {
 			return Some(port.value());
 		}
 
+		if let Some(port) = self
+			.port()
+			.downcast_ref::<OutBoundHistoryPort<T>>()
+		{
+			return Some(port.value());
+		}
+
+		if let Some(port) = self.port().downcast_ref::<ComputedPort<T>>() {
+			return Some(port.value());
+		}
+
 		None
 	}
 
@@ -140,9 +506,104 @@ impl Port {
 		}
 	}
 
+	/// Returns a [`PortChanged`](crate::PortChanged) future for the ports value, if the port has a value of type `T`.
+	#[cfg(feature = "std")]
+	pub(crate) fn changed<T: Any + Send + Sync>(&self, since: u32) -> Option<crate::PortChanged<T>> {
+		// an in port may observe the source of a converting binding
+		if let Some(port) = self.port().downcast_ref::<InBoundPort<T>>() {
+			Some(port.wait_for_change(since))
+		} else if let Some(port) = self.port().downcast_ref::<ComputedPort<T>>() {
+			Some(port.wait_for_change(since))
+		} else if let Some(cell) = self.as_lock_free_value::<T>() {
+			Some(crate::PortChanged::from_cell(cell, since))
+		} else {
+			self.as_value::<T>()
+				.map(|value| crate::PortChanged::new(value, since))
+		}
+	}
+
+	pub(crate) fn as_in_queue<T: Any + Send + Sync>(&self) -> Option<PortValuePtr<Queue<T>>> {
+		self.port()
+			.downcast_ref::<InBoundQueuePort<T>>()
+			.map(InBoundQueuePort::value)
+	}
+
+	pub(crate) fn as_out_queue<T: Any + Send + Sync>(&self) -> Option<PortValuePtr<Queue<T>>> {
+		self.port()
+			.downcast_ref::<OutBoundQueuePort<T>>()
+			.map(OutBoundQueuePort::value)
+	}
+
+	pub(crate) fn as_lock_free_cell<T: Any + Send + Sync>(&self) -> Option<Arc<LockFreeCell<T>>> {
+		self.port()
+			.downcast_ref::<LockFreeOutBoundPort<T>>()
+			.map(LockFreeOutBoundPort::cell)
+	}
+
+	/// Returns the value of a lock-free in or out port of type `T`.
+	pub(crate) fn as_lock_free_value<T: Any + Send + Sync>(&self) -> Option<Arc<LockFreeCell<T>>> {
+		self.port()
+			.downcast_ref::<LockFreeInBoundPort<T>>()
+			.map(LockFreeInBoundPort::cell)
+			.or_else(|| self.as_lock_free_cell())
+	}
+
+	/// Returns `true` if the value of type `T` is derived, i.e. computed or converted on each access,
+	/// so that setting it has no effect.
+	pub(crate) fn is_derived<T: Any + Send + Sync>(&self) -> bool {
+		self.port()
+			.downcast_ref::<InBoundPort<T>>()
+			.is_some_and(InBoundPort::is_derived)
+			|| self
+				.port()
+				.downcast_ref::<ComputedPort<T>>()
+				.is_some()
+	}
+
+	/// Returns the error for a port named `port`, which has no lockable value of type `T`.
+	pub(crate) fn no_value<T: Any + Send + Sync>(&self, port: ConstString) -> Error {
+		if self.as_lock_free_value::<T>().is_some() {
+			Error::LockFree { port }
+		} else {
+			Error::NoValueSet { port }
+		}
+	}
+
+	/// Registers a `callback` for changes of the port's value.
+	/// # Errors
+	/// - [`Error::WrongType`], if the port is not the needed port type & type of T.
+	pub(crate) fn on_change<T: Any + Send + Sync>(
+		&self,
+		callback: impl Fn(&str, u32) + Send + Sync + 'static,
+	) -> Result<()> {
+		if let Some(port) = self.port().downcast_ref::<InBoundPort<T>>() {
+			port.on_change(callback);
+			Ok(())
+		} else if let Some(port) = self.port().downcast_ref::<InOutBoundPort<T>>() {
+			port.on_change(callback);
+			Ok(())
+		} else if let Some(port) = self.port().downcast_ref::<InBoundQueuePort<T>>() {
+			port.on_change(callback);
+			Ok(())
+		} else if let Some(port) = self
+			.port()
+			.downcast_ref::<LockFreeInBoundPort<T>>()
+		{
+			port.subscribe(callback);
+			Ok(())
+		} else {
+			Err(self.wrong_type::<T>())
+		}
+	}
+
 	pub fn get<T: Any + Clone + Send + Sync>(&self) -> Option<T> {
 		if let Some(value) = self.as_in_value::<T>() {
 			value.read().get()
+		} else if let Some(port) = self
+			.port()
+			.downcast_ref::<LockFreeInBoundPort<T>>()
+		{
+			port.cell().load()
 		} else {
 			None
 		}
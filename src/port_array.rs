@@ -5,7 +5,8 @@ use core::ops::Deref;
 
 use crate::{
 	port::Port,
-	traits::{PortAccessors, PortCommons, PortProvider},
+	port_name::is_same_name,
+	traits::{PortAccessors, PortCommons, PortIterators, PortProvider},
 };
 
 /// An array like container for [`Port`]s.
@@ -28,13 +29,21 @@ impl<const S: usize> Deref for PortArray<S> {
 
 impl<const S: usize> PortAccessors for PortArray<S> {}
 
+impl<const S: usize> PortIterators for PortArray<S> {
+	fn ports(&self) -> impl Iterator<Item = &Port> {
+		self.0.iter()
+	}
+}
+
 impl<const S: usize> PortProvider for PortArray<S> {
-	fn find(&self, name: impl Into<crate::ConstString>) -> Option<&Port> {
+	fn find(&self, name: impl Into<crate::ConstString>) -> Option<Port> {
 		let name = name.into();
-		self.0
-			.iter()
-			.find(|&port| port.name() == name)
-			.map(|v| v as _)
+		self.find_index(&name)
+			.map(|index| self.0[index].clone())
+	}
+
+	fn provided_ports(&self) -> impl Iterator<Item = Port> {
+		self.0.iter().cloned()
 	}
 }
 
@@ -42,6 +51,21 @@ impl<const S: usize> PortArray<S> {
 	pub fn new(ports: [Port; S]) -> Self {
 		Self(ports)
 	}
+
+	/// Returns the position of the [`Port`] named `name`.
+	/// The position can be used with [`PortArray::get_by_index`] to avoid repeated lookups by name.
+	#[must_use]
+	pub fn find_index(&self, name: &str) -> Option<usize> {
+		self.0
+			.iter()
+			.position(|port| is_same_name(&port.name(), name))
+	}
+
+	/// Returns the [`Port`] at position `index`.
+	#[must_use]
+	pub fn get_by_index(&self, index: usize) -> Option<&Port> {
+		self.0.get(index)
+	}
 }
 
 #[cfg(test)]
@@ -98,4 +122,24 @@ mod tests {
 		assert_eq!(s.find("p3"), s.find(STATIC_NAME));
 		assert!(s.find("p_non_existent").is_none());
 	}
+
+	// test indexed lookup.
+	#[test]
+	fn index() {
+		let s = PortArray::new([
+			Port::create_in_port::<i32>("p1"),
+			Port::create_in_port::<f64>(CONST_NAME),
+			Port::create_in_port::<String>(STATIC_NAME),
+		]);
+		assert_eq!(s.find_index("p1"), Some(0));
+		assert_eq!(s.find_index(STATIC_NAME), Some(2));
+		assert_eq!(s.find_index("p_non_existent"), None);
+		assert_eq!(
+			s.get_by_index(1)
+				.map(PortCommons::name)
+				.as_deref(),
+			Some(CONST_NAME)
+		);
+		assert!(s.get_by_index(3).is_none());
+	}
 }
@@ -0,0 +1,130 @@
+// Copyright © 2025 Stephan Kunz
+//! Implementation of the [`PortChanged`] future.
+
+use core::{
+	pin::Pin,
+	task::{Context, Poll},
+};
+
+use alloc::sync::Arc;
+use portable_atomic::{AtomicUsize, Ordering};
+
+use crate::{
+	lock_free_cell::LockFreeCell,
+	port_value::{DerivedValue, PortValuePtr},
+};
+
+/// The id of the next [`PortChanged`] future, identifying the waker it registers.
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// The source of the changes.
+enum Source<T> {
+	/// A locked port value.
+	Value(PortValuePtr<T>),
+	/// A lock-free cell.
+	Cell(Arc<LockFreeCell<T>>),
+	/// A derived value.
+	Derived(Arc<dyn DerivedValue<T>>),
+}
+
+/// Future waiting for a change of a ports value.
+/// Resolves to the new sequence number, once the sequence number differs from the one it was created with.
+///
+/// The future does not need any runtime support besides a [`Waker`](core::task::Waker),
+/// so it can be used with `no_std` executors as well.
+/// It waits for the value the port is bound to at creation of the future.
+/// Dropping the future removes the waker it registered.
+#[must_use = "futures do nothing unless polled"]
+pub struct PortChanged<T> {
+	/// The observed value.
+	source: Source<T>,
+	/// The sequence number to compare with.
+	since: u32,
+	/// The id of the future, under which its waker is registered.
+	id: usize,
+	/// Whether a waker has been registered.
+	registered: bool,
+}
+
+impl<T> core::fmt::Debug for PortChanged<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("PortChanged")
+			.field("since", &self.since)
+			.finish_non_exhaustive()
+	}
+}
+
+impl<T> Future for PortChanged<T> {
+	type Output = u32;
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		let this = self.get_mut();
+		let changed = match &this.source {
+			Source::Value(value) => {
+				let mut guard = value.write();
+				let sequence_number = guard.sequence_number();
+				if sequence_number == this.since {
+					guard.register_waker(this.id, cx.waker());
+					None
+				} else {
+					Some(sequence_number)
+				}
+			}
+			Source::Cell(cell) => cell.poll_change(this.since, this.id, cx.waker()),
+			Source::Derived(derived) => derived.poll_change(this.since, this.id, cx.waker()),
+		};
+		this.registered |= changed.is_none();
+		changed.map_or(Poll::Pending, Poll::Ready)
+	}
+}
+
+impl<T> Drop for PortChanged<T> {
+	fn drop(&mut self) {
+		if self.registered {
+			match &self.source {
+				Source::Value(value) => value.write().remove_waker(self.id),
+				Source::Cell(cell) => cell.remove_waker(self.id),
+				Source::Derived(derived) => derived.remove_waker(self.id),
+			}
+		}
+	}
+}
+
+impl<T> PortChanged<T> {
+	fn with(source: Source<T>, since: u32) -> Self {
+		Self {
+			source,
+			since,
+			id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+			registered: false,
+		}
+	}
+
+	pub(crate) fn new(value: PortValuePtr<T>, since: u32) -> Self {
+		Self::with(Source::Value(value), since)
+	}
+
+	pub(crate) fn from_derived(derived: Arc<dyn DerivedValue<T>>, since: u32) -> Self {
+		Self::with(Source::Derived(derived), since)
+	}
+
+	pub(crate) fn from_cell(cell: Arc<LockFreeCell<T>>, since: u32) -> Self {
+		Self::with(Source::Cell(cell), since)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use alloc::string::String;
+
+	use super::*;
+
+	const fn is_normal<T: Sized + Send + Sync + Unpin>() {}
+
+	// check, that the auto traits are available.
+	#[test]
+	const fn normal_types() {
+		is_normal::<&PortChanged<i32>>();
+		is_normal::<PortChanged<String>>();
+	}
+}
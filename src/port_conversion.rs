@@ -0,0 +1,195 @@
+// Copyright © 2025 Stephan Kunz
+//! Converting bindings between [`Port`]s of different value types.
+
+use core::{
+	any::{Any, TypeId},
+	task::Waker,
+};
+
+use alloc::{
+	collections::btree_map::BTreeMap,
+	string::{String, ToString},
+	sync::Arc,
+};
+
+use crate::{
+	RwLock,
+	port::Port,
+	port_value::{DerivedValue, Observer, PortValue, PortValuePtr},
+};
+
+/// Function converting a value of type `S` into a value of type `D`.
+pub(crate) type ConvertFn<S, D> = Arc<dyn Fn(&S) -> D + Send + Sync>;
+
+/// The global registry of conversions by the [`TypeId`]s of source and destination type.
+/// The entries are [`ConvertFn`]s.
+static CONVERSIONS: RwLock<BTreeMap<(TypeId, TypeId), Arc<dyn Any + Send + Sync>>> = RwLock::new(BTreeMap::new());
+
+/// The value of type `S` of a bound port together with the conversion into `D`.
+pub(crate) struct Converted<S, D> {
+	/// The source value, which may be derived itself.
+	source: Arc<dyn DerivedValue<S>>,
+	/// The conversion.
+	convert: ConvertFn<S, D>,
+	/// The last converted value, which is replaced by a new one when the source value has changed.
+	/// The lock is only held while accessing the cache, never by a guard of the converted value.
+	cache: RwLock<Cache<D>>,
+}
+
+/// A converted value together with the state of the source value it was converted from.
+struct Cache<D> {
+	/// The converted value.
+	value: PortValuePtr<D>,
+	/// The sequence number and presence of the source value at the conversion.
+	state: (u32, bool),
+}
+
+impl<S, D> Converted<S, D> {
+	pub(crate) fn new(source: Arc<dyn DerivedValue<S>>, convert: ConvertFn<S, D>) -> Self {
+		Self {
+			source,
+			convert,
+			cache: RwLock::new(Cache {
+				value: PortValuePtr::new(PortValue::default()),
+				state: (0, false),
+			}),
+		}
+	}
+}
+
+impl<S: Send + Sync, D: Send + Sync> DerivedValue<D> for Converted<S, D> {
+	fn value(&self) -> PortValuePtr<D> {
+		let source = self.source.value();
+		let source = source.read();
+		let state = (source.sequence_number(), source.is_some());
+		{
+			let cache = self.cache.read();
+			if cache.state == state {
+				return cache.value.clone();
+			}
+		}
+		let mut cache = self.cache.write();
+		// another thread may have converted the value in between
+		if cache.state != state {
+			let mut value = PortValue::default();
+			value.mirror(source.as_ref().map(|value| (self.convert)(value)), state.0);
+			*cache = Cache {
+				value: PortValuePtr::new(value),
+				state,
+			};
+		}
+		cache.value.clone()
+	}
+
+	fn address(&self) -> usize {
+		self.source.address()
+	}
+
+	fn subscribe(&self, observer: Observer) {
+		self.source.subscribe(observer);
+	}
+
+	fn unsubscribe(&self, observer: &Observer) {
+		self.source.unsubscribe(observer);
+	}
+
+	fn poll_change(&self, since: u32, id: usize, waker: &Waker) -> Option<u32> {
+		self.source.poll_change(since, id, waker)
+	}
+
+	fn remove_waker(&self, id: usize) {
+		self.source.remove_waker(id);
+	}
+}
+
+/// Returns the conversion from `S` into `D`, either registered or builtin.
+pub(crate) fn conversion<S: Any, D: Any>() -> Option<ConvertFn<S, D>> {
+	let registered = CONVERSIONS
+		.read()
+		.get(&(TypeId::of::<S>(), TypeId::of::<D>()))
+		.cloned();
+	registered
+		.and_then(|convert| convert.downcast_ref::<ConvertFn<S, D>>().cloned())
+		.or_else(builtin::<S, D>)
+}
+
+/// Returns the builtin conversion from `S` into `D`.
+fn builtin<S: Any, D: Any>() -> Option<ConvertFn<S, D>> {
+	/// Returns `convert` as [`ConvertFn`]`<S, D>`, if it has the wanted types.
+	fn erased<S: Any, D: Any, S2: Any, D2: Any>(
+		convert: impl Fn(&S2) -> D2 + Send + Sync + 'static,
+	) -> Option<ConvertFn<S, D>> {
+		let convert: ConvertFn<S2, D2> = Arc::new(convert);
+		(&convert as &dyn Any)
+			.downcast_ref::<ConvertFn<S, D>>()
+			.cloned()
+	}
+
+	macro_rules! lossless {
+		($src:ty => $($dst:ty),*) => {
+			if TypeId::of::<S>() == TypeId::of::<$src>() {
+				$(
+					if TypeId::of::<D>() == TypeId::of::<$dst>() {
+						return erased::<S, D, $src, $dst>(|value: &$src| <$dst>::from(*value));
+					}
+				)*
+			}
+		};
+	}
+
+	macro_rules! to_string {
+		($($src:ty),*) => {
+			if TypeId::of::<D>() == TypeId::of::<String>() {
+				$(
+					if TypeId::of::<S>() == TypeId::of::<$src>() {
+						return erased::<S, D, $src, String>(<$src>::to_string);
+					}
+				)*
+			}
+		};
+	}
+
+	lossless!(i8 => i16, i32, i64, i128, isize, f32, f64);
+	lossless!(i16 => i32, i64, i128, isize, f32, f64);
+	lossless!(i32 => i64, i128, f64);
+	lossless!(i64 => i128);
+	lossless!(u8 => u16, u32, u64, u128, usize, i16, i32, i64, i128, isize, f32, f64);
+	lossless!(u16 => u32, u64, u128, usize, i32, i64, i128, f32, f64);
+	lossless!(u32 => u64, u128, i64, i128, f64);
+	lossless!(u64 => u128, i128);
+	lossless!(f32 => f64);
+	to_string!(
+		bool, char, f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+	);
+	None
+}
+
+impl Port {
+	/// Registers `convert` for converting bindings from values of type `S` into values of type `D`,
+	/// replacing a previously registered or builtin conversion.
+	///
+	/// Builtin are the lossless conversions between the numeric primitives via [`From`]
+	/// and the conversions of `bool`, `char` and the numeric primitives into [`String`].
+	pub fn register_conversion<S: Any, D: Any>(convert: impl Fn(&S) -> D + Send + Sync + 'static) {
+		let convert: ConvertFn<S, D> = Arc::new(convert);
+		CONVERSIONS
+			.write()
+			.insert((TypeId::of::<S>(), TypeId::of::<D>()), Arc::new(convert));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#![allow(clippy::unwrap_used)]
+	use super::*;
+
+	#[test]
+	fn builtins() {
+		assert_eq!(builtin::<f32, f64>().unwrap()(&1.5), 1.5);
+		assert_eq!(builtin::<u8, i32>().unwrap()(&255), 255);
+		assert_eq!(builtin::<i32, String>().unwrap()(&-42), "-42");
+		assert!(builtin::<f64, f32>().is_none());
+		assert!(builtin::<i32, u32>().is_none());
+		assert!(builtin::<String, i32>().is_none());
+	}
+}
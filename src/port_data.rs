@@ -3,28 +3,49 @@
 
 use core::any::Any;
 
-use alloc::sync::Arc;
+use alloc::{sync::Arc, vec::Vec};
 
 use crate::{
 	ConstString, RwLock,
 	error::{Error, Result},
-	port_value::{PortValue, PortValuePtr, PortValueReadGuard, PortValueWriteGuard},
+	port_changed::PortChanged,
+	port_value::{DerivedValue, Observer, PortValue, PortValuePtr, PortValueReadGuard, PortValueWriteGuard},
 	traits::{InBound, InOutBound, OutBound, PortCommons},
 };
 
+/// Function creating the default value of a port.
+type DefaultFn<T> = Arc<dyn Fn() -> T + Send + Sync>;
+
 /// PortData.
 pub(crate) struct PortData<T> {
 	/// An identifying name of the port, which must be unique for a given [`PortProvider`](crate::traits::PortProvider).
 	name: ConstString,
 	/// The current value `T` of the port together with its [`SequenceNumber`](crate::sequence_number::SequenceNumber).
+	/// With a derived value, it is unused and the value is taken from that on each access.
 	value: PortValuePtr<T>,
+	/// The name of the source port, if the port is bound.
+	source: Option<ConstString>,
+	/// The derived value, if the port is bound to one, like with a converting binding.
+	derived: Option<Arc<dyn DerivedValue<T>>>,
+	/// The [`Observer`]s registered via this port, they follow the port when (re)binding.
+	observers: RwLock<Vec<Observer>>,
+	/// Creates the value of the port while it is not bound.
+	default: Option<DefaultFn<T>>,
+	/// Whether the port must be bound before use.
+	required: bool,
 }
 
 impl<T> Clone for PortData<T> {
+	/// A clone does not take over the [`Observer`]s.
 	fn clone(&self) -> Self {
 		Self {
 			name: self.name.clone(),
 			value: self.value.clone(),
+			source: self.source.clone(),
+			derived: self.derived.clone(),
+			observers: RwLock::new(Vec::new()),
+			default: self.default.clone(),
+			required: self.required,
 		}
 	}
 }
@@ -33,6 +54,8 @@ impl<T> core::fmt::Debug for PortData<T> {
 	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
 		f.debug_struct("PortData")
 			.field("name", &self.name)
+			.field("source", &self.source)
+			.field("required", &self.required)
 			//.field("value", &self.value)
 			.finish_non_exhaustive()
 	}
@@ -42,8 +65,9 @@ impl<T: 'static> PartialEq for PortData<T> {
 	/// Partial equality of a port is, if name and value type are the same
 	fn eq(&self, other: &Self) -> bool {
 		if self.name == other.name {
-			let v1 = self.value.read();
-			let v2 = other.value.read();
+			let (value1, value2) = (self.current(), other.current());
+			let v1 = value1.read();
+			let v2 = value2.read();
 			if let Some(value1) = v1.as_ref()
 				&& let Some(value2) = v2.as_ref()
 			{
@@ -65,7 +89,23 @@ impl<T> PortCommons for PortData<T> {
 	}
 
 	fn sequence_number(&self) -> u32 {
-		self.value.read().sequence_number()
+		self.current().read().sequence_number()
+	}
+
+	fn is_bound(&self) -> bool {
+		self.source.is_some()
+	}
+
+	fn bound_source_name(&self) -> Option<ConstString> {
+		self.source.clone()
+	}
+
+	fn is_required(&self) -> bool {
+		self.required
+	}
+
+	fn has_default(&self) -> bool {
+		self.default.is_some()
 	}
 }
 
@@ -74,47 +114,63 @@ impl<T> InBound<T> for PortData<T> {
 	where
 		T: Clone,
 	{
-		self.value.read().get()
+		self.current().read().get()
 	}
 
 	fn read(&self) -> Result<PortValueReadGuard<T>> {
+		let value = self.current();
 		// Test for value is separate to not pass a locked value into the guard.
-		let has_value = self.value.read().is_some();
+		let has_value = value.read().is_some();
 		if has_value {
-			PortValueReadGuard::new(self.name.clone(), self.value.clone())
+			PortValueReadGuard::new(self.name.clone(), value)
 		} else {
 			Err(Error::NoValueSet { port: self.name.clone() })
 		}
 	}
 
 	fn try_read(&self) -> Result<PortValueReadGuard<T>> {
+		let value = self.current();
 		// Test for value is separate to not pass a locked value into the guard.
-		let has_value = if let Some(guard) = self.value.try_read() {
+		let has_value = if let Some(guard) = value.try_read() {
 			guard.is_some()
 		} else {
 			return Err(Error::IsLocked { port: self.name.clone() });
 		};
 		if has_value {
-			PortValueReadGuard::try_new(self.name.clone(), self.value.clone())
+			PortValueReadGuard::try_new(self.name.clone(), value)
 		} else {
 			Err(Error::NoValueSet { port: self.name.clone() })
 		}
 	}
+
+	fn on_change(&self, callback: impl Fn(&str, u32) + Send + Sync + 'static) {
+		let observer = Observer::new(self.name.clone(), Arc::new(callback));
+		self.subscribe(observer.clone());
+		self.observers.write().push(observer);
+	}
+
+	fn wait_for_change(&self, since: u32) -> PortChanged<T> {
+		if let Some(derived) = &self.derived {
+			PortChanged::from_derived(derived.clone(), since)
+		} else {
+			PortChanged::new(self.value.clone(), since)
+		}
+	}
 }
 
 impl<T> InOutBound<T> for PortData<T> {
 	fn replace(&self, value: impl Into<T>) -> Option<T> {
-		self.value.write().replace(value.into())
+		self.value.replace(value)
 	}
 
 	fn take(&self) -> Option<T> {
-		self.value.write().take()
+		self.value.take()
 	}
 }
 
 impl<T> OutBound<T> for PortData<T> {
 	fn set(&self, value: impl Into<T>) {
-		self.value.write().set(value.into())
+		self.value.set(value)
 	}
 
 	fn write(&self) -> Result<PortValueWriteGuard<T>> {
@@ -147,7 +203,12 @@ impl<T> PortData<T> {
 	pub(crate) fn new(name: impl Into<ConstString>) -> Self {
 		Self {
 			name: name.into(),
-			value: Arc::new(RwLock::new(PortValue::default())),
+			value: PortValuePtr::new(PortValue::default()),
+			source: None,
+			derived: None,
+			observers: RwLock::new(Vec::new()),
+			default: None,
+			required: false,
 		}
 	}
 
@@ -155,16 +216,150 @@ impl<T> PortData<T> {
 	pub(crate) fn with_value(name: impl Into<ConstString>, value: impl Into<T>) -> Self {
 		Self {
 			name: name.into(),
-			value: Arc::new(RwLock::new(PortValue::new(value.into()))),
+			value: PortValuePtr::new(PortValue::new(value.into())),
+			source: None,
+			derived: None,
+			observers: RwLock::new(Vec::new()),
+			default: None,
+			required: false,
+		}
+	}
+
+	/// Creates [`PortData`] with an initial `value`, which does not count as a change.
+	#[must_use]
+	pub(crate) fn with_initial_value(name: impl Into<ConstString>, value: impl Into<T>) -> Self {
+		Self {
+			name: name.into(),
+			value: PortValuePtr::new(PortValue::initial(value.into())),
+			source: None,
+			derived: None,
+			observers: RwLock::new(Vec::new()),
+			default: None,
+			required: false,
 		}
 	}
 
+	/// Creates [`PortData`] with a `default` value, which is restored on unbinding and does not count as a change.
+	#[must_use]
+	pub(crate) fn with_default(name: impl Into<ConstString>, default: T) -> Self
+	where
+		T: Clone + Send + Sync + 'static,
+	{
+		let mut data = Self::with_initial_value(name, default.clone());
+		data.default = Some(Arc::new(move || default.clone()));
+		data
+	}
+
+	/// Marks the port as required to be bound.
+	pub(crate) const fn set_required(&mut self) {
+		self.required = true;
+	}
+
 	pub(crate) fn value(&self) -> PortValuePtr<T> {
-		self.value.clone()
+		self.current()
+	}
+
+	/// Returns the address of the value, with a derived value the address of the observed value.
+	pub(crate) fn value_address(&self) -> usize {
+		self.derived
+			.as_ref()
+			.map_or_else(|| self.value.address(), |derived| derived.address())
 	}
 
 	pub(crate) fn set_value(&mut self, value: PortValuePtr<T>) {
-		self.value = value
+		self.observe(value, None);
+	}
+
+	/// Replaces the value and the derived value, the observers registered via this port follow the port.
+	fn observe(&mut self, value: PortValuePtr<T>, derived: Option<Arc<dyn DerivedValue<T>>>) {
+		let observers = self.observers.read().clone();
+		for observer in &observers {
+			self.unsubscribe(observer);
+		}
+		self.value = value;
+		self.derived = derived;
+		for observer in observers {
+			self.subscribe(observer);
+		}
+	}
+
+	/// Adds an [`Observer`] to the observed value, which may be given by the derived value.
+	fn subscribe(&self, observer: Observer) {
+		if let Some(derived) = &self.derived {
+			derived.subscribe(observer);
+		} else {
+			self.value.subscribe(observer);
+		}
+	}
+
+	/// Removes an [`Observer`] from the observed value.
+	fn unsubscribe(&self, observer: &Observer) {
+		if let Some(derived) = &self.derived {
+			derived.unsubscribe(observer);
+		} else {
+			self.value.unsubscribe(observer);
+		}
+	}
+
+	/// Returns `true` if the port is bound to a derived value.
+	pub(crate) const fn is_derived(&self) -> bool {
+		self.derived.is_some()
+	}
+
+	/// Returns the current value, with a derived value the one taken from that.
+	fn current(&self) -> PortValuePtr<T> {
+		self.derived
+			.as_ref()
+			.map_or_else(|| self.value.clone(), |derived| derived.value())
+	}
+
+	/// Binds the port to the `value` of the port named `source`.
+	/// # Errors
+	/// - [`Error::AlreadyBound`] if the port is already bound.
+	pub(crate) fn bind(&mut self, source: impl Into<ConstString>, value: PortValuePtr<T>) -> Result<()> {
+		if self.source.is_some() {
+			return Err(Error::AlreadyBound { port: self.name.clone() });
+		}
+		self.rebind(source, value);
+		Ok(())
+	}
+
+	/// Binds the port to the `value` of the port named `source`, replacing an existing binding.
+	pub(crate) fn rebind(&mut self, source: impl Into<ConstString>, value: PortValuePtr<T>) {
+		self.source = Some(source.into());
+		self.set_value(value);
+	}
+
+	/// Binds the port to the `derived` value of the port named `source`.
+	/// # Errors
+	/// - [`Error::AlreadyBound`] if the port is already bound.
+	pub(crate) fn bind_derived(&mut self, source: impl Into<ConstString>, derived: Arc<dyn DerivedValue<T>>) -> Result<()> {
+		if self.source.is_some() {
+			return Err(Error::AlreadyBound { port: self.name.clone() });
+		}
+		self.rebind_derived(source, derived);
+		Ok(())
+	}
+
+	/// Binds the port to the `derived` value of the port named `source`, replacing an existing binding.
+	pub(crate) fn rebind_derived(&mut self, source: impl Into<ConstString>, derived: Arc<dyn DerivedValue<T>>) {
+		self.source = Some(source.into());
+		self.observe(PortValuePtr::new(PortValue::default()), Some(derived));
+	}
+
+	/// Removes the binding of the port, the port gets a new value, which is empty or the default value.
+	/// # Errors
+	/// - [`Error::NotBound`] if the port is not bound.
+	pub(crate) fn unbind(&mut self) -> Result<()> {
+		if self.source.take().is_none() {
+			return Err(Error::NotBound { port: self.name.clone() });
+		}
+		let value = self
+			.default
+			.as_ref()
+			.map_or_else(PortValue::default, |default| PortValue::initial(default()));
+		self.set_value(PortValuePtr::new(value));
+		Ok(())
 	}
 }
 
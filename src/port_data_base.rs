@@ -6,7 +6,7 @@ use core::any::Any;
 use alloc::collections::btree_map::BTreeMap;
 
 use crate::{
-	ConstString, PortAccessors, PortProvider,
+	ConstString, PortAccessors, PortIterators, PortProvider,
 	error::{Error, Result},
 	in_out_port::InOutBoundPort,
 	port::Port,
@@ -27,18 +27,23 @@ impl core::fmt::Debug for PortDataBase {
 }
 
 impl PortProvider for PortDataBase {
-	fn find(&self, name: impl Into<ConstString>) -> Option<&Port> {
-		let name = name.into();
-		self.0
-			.values()
-			.into_iter()
-			.find(|&port| port.name() == name)
-			.map(|v| v as _)
+	fn find(&self, name: impl Into<ConstString>) -> Option<Port> {
+		self.0.get(&name.into()).cloned()
+	}
+
+	fn provided_ports(&self) -> impl Iterator<Item = Port> {
+		self.0.values().cloned()
 	}
 }
 
 impl PortAccessors for PortDataBase {}
 
+impl PortIterators for PortDataBase {
+	fn ports(&self) -> impl Iterator<Item = &Port> {
+		self.0.values()
+	}
+}
+
 impl PortDataBase {
 	/// Returns `true` if a [`Port`] with name `key` is available, otherwise `false`.
 	#[must_use]
@@ -52,7 +57,7 @@ impl PortDataBase {
 	pub fn contains<T: Any + Send + Sync>(&self, key: &str) -> Result<bool> {
 		self.0.get(key).map_or(Ok(false), |port| {
 			port.as_in_out_port::<T>().map_or_else(
-				|| Err(Error::WrongType { port: key.into() }),
+				|| Err(port.wrong_type::<T>()),
 				|port| if port.name().as_ref() == key { Ok(true) } else { Ok(false) },
 			)
 		})
@@ -82,7 +87,7 @@ impl PortDataBase {
 				|| Err(Error::NotFound { port: key.into() }),
 				|port| {
 					port.as_in_out_port::<T>().map_or_else(
-						|| Err(Error::WrongType { port: key.into() }),
+						|| Err(port.wrong_type::<T>()),
 						|port| {
 							port.take()
 								.map_or_else(|| Err(Error::NoValueSet { port: key.into() }), |value| Ok(value))
@@ -103,6 +108,38 @@ impl PortDataBase {
 			.map_or_else(|| Err(Error::NotFound { port: key.into() }), |port| Ok(port.clone()))
 	}
 
+	/// Returns an iterator over all entries.
+	pub(crate) fn entries(&self) -> impl Iterator<Item = (&ConstString, &Port)> {
+		self.0.iter()
+	}
+
+	/// Sets the value of type `T` stored under `key`, creating the [`Port`] if `key` is not contained.
+	/// # Errors
+	/// - [`Error::WrongType`] if the [`Port`] has not the expected type `T`.
+	pub(crate) fn load<T: Any + Send + Sync>(&mut self, key: ConstString, value: Option<T>) -> Result<()> {
+		if let Some(port) = self.0.get(&key) {
+			let port = port
+				.as_in_out_port::<T>()
+				.ok_or_else(|| port.wrong_type::<T>())?;
+			match value {
+				Some(value) => drop(port.replace(value)),
+				None => drop(port.take()),
+			}
+		} else {
+			let iop = value.map_or_else(
+				|| InOutBoundPort::<T>::new(key.clone()),
+				|value| InOutBoundPort::<T>::with_value(key.clone(), value),
+			);
+			self.0.insert(key, Port::from(iop));
+		}
+		Ok(())
+	}
+
+	/// Removes the [`Port`] stored under `key` regardless of its type.
+	pub(crate) fn remove(&mut self, key: &str) -> Option<Port> {
+		self.0.remove(key)
+	}
+
 	/// Updates a value of type `T` stored under `key` and returns the old value.
 	/// # Errors
 	/// - [`Error::NotFound`]  if `key` is not contained.
@@ -111,10 +148,8 @@ impl PortDataBase {
 		self.0.get(key).map_or_else(
 			|| Err(Error::NotFound { port: key.into() }),
 			|port| {
-				port.as_in_out_port::<T>().map_or_else(
-					|| Err(Error::WrongType { port: key.into() }),
-					|port| Ok(port.replace(value.into())),
-				)
+				port.as_in_out_port::<T>()
+					.map_or_else(|| Err(port.wrong_type::<T>()), |port| Ok(port.replace(value.into())))
 			},
 		)
 	}
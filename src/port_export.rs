@@ -0,0 +1,272 @@
+// Copyright © 2025 Stephan Kunz
+//! Implementation of [`PortExport`] describing ports and their bindings as Graphviz DOT or JSON.
+
+use core::fmt::{Display, Formatter, Result as FmtResult, Write};
+
+use alloc::{collections::btree_map::BTreeMap, vec::Vec};
+
+use crate::{
+	ConstString,
+	error::{Error, Result},
+	port::Port,
+	traits::{PortCommons, PortProvider},
+};
+
+/// PortExport
+///
+/// Collects the [`Port`]s of several [`PortProvider`]s and describes them as Graphviz DOT or JSON.
+/// Each port is described with its name, direction, type name and sequence number.
+/// A binding is described as an edge from the source port to the bound port,
+/// if both ports share the same value.
+///
+/// The description reflects the state at the time of formatting.
+#[derive(Debug, Default)]
+pub struct PortExport {
+	/// The providers with their ports in the order of adding.
+	providers: Vec<(ConstString, Vec<Port>)>,
+}
+
+/// A port within a [`PortExport`], identified by the name of its provider.
+type Endpoint<'a> = (&'a str, &'a Port);
+
+impl PortExport {
+	/// Adds the ports of `provider` under `name`.
+	/// # Errors
+	/// - [`Error::AlreadyExists`] if a provider with that name already exists.
+	pub fn add_provider(&mut self, name: impl Into<ConstString>, provider: &impl PortProvider) -> Result<()> {
+		let name = name.into();
+		if self
+			.providers
+			.iter()
+			.any(|(existing, _)| *existing == name)
+		{
+			return Err(Error::AlreadyExists { port: name });
+		}
+		self.providers
+			.push((name, provider.provided_ports().collect()));
+		Ok(())
+	}
+
+	/// Returns a formatter for the Graphviz DOT description.
+	/// Each provider is a cluster of port nodes named `"<provider>.<port>"`.
+	pub fn dot(&self) -> impl Display + '_ {
+		Dot(self)
+	}
+
+	/// Returns a formatter for the JSON description.
+	pub fn json(&self) -> impl Display + '_ {
+		Json(self)
+	}
+
+	/// Returns the bindings between the collected ports as pairs of source and bound port.
+	fn edges(&self) -> Vec<(Endpoint<'_>, Endpoint<'_>)> {
+		let mut shared: BTreeMap<usize, Vec<Endpoint<'_>>> = BTreeMap::new();
+		for (provider, ports) in &self.providers {
+			for port in ports {
+				shared
+					.entry(port.value_address())
+					.or_default()
+					.push((provider, port));
+			}
+		}
+
+		let mut edges = Vec::new();
+		for (provider, ports) in &self.providers {
+			for port in ports {
+				if let Some(source) = port.bound_source_name()
+					&& let Some(candidates) = shared.get(&port.value_address())
+					&& let Some(from) = candidates.iter().find(|(other, candidate)| {
+						(*other != &**provider || candidate.name() != port.name())
+							&& candidate.direction().is_output()
+							&& candidate.name() == source
+					}) {
+					edges.push((*from, (&**provider, port)));
+				}
+			}
+		}
+		edges
+	}
+}
+
+/// Formatter for the Graphviz DOT description.
+struct Dot<'a>(&'a PortExport);
+
+impl Display for Dot<'_> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		writeln!(f, "digraph ports {{")?;
+		writeln!(f, "\trankdir=LR;")?;
+		writeln!(f, "\tnode [shape=box];")?;
+		for (index, (provider, ports)) in self.0.providers.iter().enumerate() {
+			writeln!(f, "\tsubgraph cluster_{index} {{")?;
+			write!(f, "\t\tlabel=")?;
+			dot_string(f, provider)?;
+			writeln!(f, ";")?;
+			for port in ports {
+				write!(f, "\t\t")?;
+				dot_id(f, provider, &port.name())?;
+				write!(f, " [label=")?;
+				dot_string(
+					f,
+					&alloc::format!(
+						"{}\n{} {}\n#{}",
+						port.name(),
+						port.direction(),
+						port.type_name(),
+						port.sequence_number()
+					),
+				)?;
+				writeln!(f, "];")?;
+			}
+			writeln!(f, "\t}}")?;
+		}
+		for ((from_provider, from), (to_provider, to)) in self.0.edges() {
+			write!(f, "\t")?;
+			dot_id(f, from_provider, &from.name())?;
+			write!(f, " -> ")?;
+			dot_id(f, to_provider, &to.name())?;
+			writeln!(f, ";")?;
+		}
+		writeln!(f, "}}")
+	}
+}
+
+/// Writes the id of a port node.
+fn dot_id(f: &mut Formatter<'_>, provider: &str, port: &str) -> FmtResult {
+	dot_string(f, &alloc::format!("{provider}.{port}"))
+}
+
+/// Writes a quoted DOT string.
+fn dot_string(f: &mut Formatter<'_>, value: &str) -> FmtResult {
+	f.write_char('"')?;
+	for c in value.chars() {
+		match c {
+			'"' => f.write_str("\\\"")?,
+			'\\' => f.write_str("\\\\")?,
+			'\n' => f.write_str("\\n")?,
+			c => f.write_char(c)?,
+		}
+	}
+	f.write_char('"')
+}
+
+/// Formatter for the JSON description.
+struct Json<'a>(&'a PortExport);
+
+impl Display for Json<'_> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		f.write_str("{\"providers\":[")?;
+		for (index, (provider, ports)) in self.0.providers.iter().enumerate() {
+			if index > 0 {
+				f.write_char(',')?;
+			}
+			f.write_str("{\"name\":")?;
+			json_string(f, provider)?;
+			f.write_str(",\"ports\":[")?;
+			for (index, port) in ports.iter().enumerate() {
+				if index > 0 {
+					f.write_char(',')?;
+				}
+				f.write_str("{\"name\":")?;
+				json_string(f, &port.name())?;
+				write!(f, ",\"direction\":\"{}\",\"type\":", port.direction())?;
+				json_string(f, port.type_name())?;
+				write!(f, ",\"sequence_number\":{}}}", port.sequence_number())?;
+			}
+			f.write_str("]}")?;
+		}
+		f.write_str("],\"edges\":[")?;
+		for (index, ((from_provider, from), (to_provider, to))) in self.0.edges().into_iter().enumerate() {
+			if index > 0 {
+				f.write_char(',')?;
+			}
+			f.write_str("{\"from\":")?;
+			json_endpoint(f, from_provider, &from.name())?;
+			f.write_str(",\"to\":")?;
+			json_endpoint(f, to_provider, &to.name())?;
+			f.write_char('}')?;
+		}
+		f.write_str("]}")
+	}
+}
+
+/// Writes a JSON object identifying a port.
+fn json_endpoint(f: &mut Formatter<'_>, provider: &str, port: &str) -> FmtResult {
+	f.write_str("{\"provider\":")?;
+	json_string(f, provider)?;
+	f.write_str(",\"port\":")?;
+	json_string(f, port)?;
+	f.write_char('}')
+}
+
+/// Writes a quoted JSON string.
+fn json_string(f: &mut Formatter<'_>, value: &str) -> FmtResult {
+	f.write_char('"')?;
+	for c in value.chars() {
+		match c {
+			'"' => f.write_str("\\\"")?,
+			'\\' => f.write_str("\\\\")?,
+			'\n' => f.write_str("\\n")?,
+			'\r' => f.write_str("\\r")?,
+			'\t' => f.write_str("\\t")?,
+			c if c.is_control() => write!(f, "\\u{:04x}", u32::from(c))?,
+			c => f.write_char(c)?,
+		}
+	}
+	f.write_char('"')
+}
+
+#[cfg(test)]
+mod tests {
+	#![allow(clippy::unwrap_used)]
+	use alloc::{string::ToString, vec};
+
+	use crate::{PortAccessors, port_list::PortList};
+
+	use super::*;
+
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	// check, that the auto traits are available.
+	#[test]
+	const fn normal_types() {
+		is_normal::<&PortExport>();
+		is_normal::<PortExport>();
+	}
+
+	#[test]
+	fn escaping() {
+		let list = PortList::new(vec![Port::create_in_port::<i32>("a\"b")]);
+		let mut export = PortExport::default();
+		export.add_provider("n\\1", &list).unwrap();
+		assert!(export.add_provider("n\\1", &list).is_err());
+		assert_eq!(
+			export.json().to_string(),
+			r#"{"providers":[{"name":"n\\1","ports":[{"name":"a\"b","direction":"in","type":"i32","sequence_number":0}]}],"edges":[]}"#
+		);
+		assert!(
+			export
+				.dot()
+				.to_string()
+				.contains(r#""n\\1.a\"b" [label="a\"b\nin i32\n#0"];"#)
+		);
+	}
+
+	#[test]
+	fn edges() {
+		let outputs = PortList::new(vec![Port::create_out_port::<i32>("o")]);
+		let inputs = PortList::new(vec![
+			Port::create_in_port::<i32>("i1"),
+			Port::create_in_port::<i32>("i2"),
+		]);
+		inputs
+			.bind_to::<i32>("i1", &outputs, "o")
+			.unwrap();
+		let mut export = PortExport::default();
+		export.add_provider("out", &outputs).unwrap();
+		export.add_provider("in", &inputs).unwrap();
+		let edges = export.edges();
+		assert_eq!(edges.len(), 1);
+		assert_eq!(edges[0].0.0, "out");
+		assert_eq!(edges[0].1.1.name().as_ref(), "i1");
+	}
+}
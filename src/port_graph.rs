@@ -0,0 +1,356 @@
+// Copyright © 2025 Stephan Kunz
+//! Implementation of a [`PortGraph`] validating the bindings between port providers.
+
+use core::any::{Any, TypeId};
+
+use alloc::{collections::btree_map::BTreeMap, vec::Vec};
+
+use crate::{
+	ConstString,
+	error::{Error, Result},
+	port::Port,
+	traits::{PortCommons, PortProvider},
+};
+
+/// An issue found in a [`PortGraph`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum GraphIssue {
+	/// A binding refers to a node, which is not part of the graph.
+	NodeNotFound {
+		/// Name of the node.
+		node: ConstString,
+	},
+	/// A binding refers to a port, which is not provided by the node.
+	PortNotFound {
+		/// Name of the node.
+		node: ConstString,
+		/// Name of the port.
+		port: ConstString,
+	},
+	/// A binding uses a port in the wrong direction,
+	/// either an input port as source or an output port as destination.
+	WrongDirection {
+		/// Name of the node.
+		node: ConstString,
+		/// Name of the port.
+		port: ConstString,
+	},
+	/// A port has another type than the binding.
+	WrongType {
+		/// Name of the node.
+		node: ConstString,
+		/// Name of the port.
+		port: ConstString,
+		/// Name of the type of the binding.
+		expected: &'static str,
+		/// Name of the type of the port.
+		actual: &'static str,
+	},
+	/// An input port is the destination of more than one binding or is already bound.
+	BoundTwice {
+		/// Name of the node.
+		node: ConstString,
+		/// Name of the port.
+		port: ConstString,
+	},
+	/// An input port is neither bound nor the destination of a binding,
+	/// although it is required or has no default value.
+	Unbound {
+		/// Name of the node.
+		node: ConstString,
+		/// Name of the port.
+		port: ConstString,
+	},
+	/// The bindings form a cycle through in/out ports.
+	Cycle {
+		/// The node and port names of the cycle, in the direction of the data flow.
+		ports: Vec<(ConstString, ConstString)>,
+	},
+	/// Binding the ports failed.
+	BindingFailed {
+		/// Name of the node of the destination port.
+		node: ConstString,
+		/// Name of the destination port.
+		port: ConstString,
+		/// The reason.
+		error: Error,
+	},
+}
+
+/// The result of validating a [`PortGraph`].
+#[derive(Debug, Default)]
+pub struct GraphReport {
+	/// The issues found.
+	issues: Vec<GraphIssue>,
+}
+
+impl GraphReport {
+	/// Returns `true` if no issues have been found.
+	#[must_use]
+	pub const fn is_ok(&self) -> bool {
+		self.issues.is_empty()
+	}
+
+	/// Returns the issues found.
+	#[must_use]
+	pub fn issues(&self) -> &[GraphIssue] {
+		&self.issues
+	}
+}
+
+/// A binding of an input port to an output port with a value of a certain type.
+struct Edge {
+	/// Node and port consuming the value.
+	input: (ConstString, ConstString),
+	/// Node and port producing the value.
+	output: (ConstString, ConstString),
+	/// [`TypeId`] of the value.
+	type_id: TypeId,
+	/// Name of the type of the value.
+	type_name: &'static str,
+	/// Binds the input port to the output port.
+	bind: fn(&Port, &Port) -> Result<()>,
+}
+
+/// PortGraph
+///
+/// Collects the [`Port`]s of several [`PortProvider`]s as named nodes together with the bindings between them.
+/// The whole wiring can then be validated at once with [`PortGraph::validate`],
+/// and bound with [`PortGraph::bind`], if it is valid.
+#[derive(Default)]
+pub struct PortGraph {
+	/// The ports of the nodes.
+	nodes: BTreeMap<ConstString, Vec<Port>>,
+	/// The recorded bindings.
+	edges: Vec<Edge>,
+}
+
+impl core::fmt::Debug for PortGraph {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("PortGraph")
+			.field("nodes", &self.nodes)
+			.finish_non_exhaustive()
+	}
+}
+
+impl PortGraph {
+	/// Adds the ports of `provider` as node `name`.
+	/// The graph shares the state of the ports with the provider.
+	/// # Errors
+	/// - [`Error::AlreadyExists`] if a node with that name already exists.
+	pub fn add_node(&mut self, name: impl Into<ConstString>, provider: &impl PortProvider) -> Result<()> {
+		let name = name.into();
+		if self.nodes.contains_key(&name) {
+			return Err(Error::AlreadyExists { port: name });
+		}
+		self.nodes
+			.insert(name, provider.provided_ports().collect());
+		Ok(())
+	}
+
+	/// Records a binding of the port `in_port` of node `in_node` to the port `out_port` of node `out_node`
+	/// with a value of type `T`.
+	/// Port `out_port` is where the value is created, `in_port` where it is consumed.
+	/// Nothing is checked or bound until [`PortGraph::validate`] or [`PortGraph::bind`] are called.
+	pub fn bind_to<T: Any + Send + Sync>(
+		&mut self,
+		in_node: impl Into<ConstString>,
+		in_port: impl Into<ConstString>,
+		out_node: impl Into<ConstString>,
+		out_port: impl Into<ConstString>,
+	) {
+		self.edges.push(Edge {
+			input: (in_node.into(), in_port.into()),
+			output: (out_node.into(), out_port.into()),
+			type_id: TypeId::of::<T>(),
+			type_name: core::any::type_name::<T>(),
+			bind: |in_port, out_port| in_port.bind::<T>(out_port, false),
+		});
+	}
+
+	/// Validates the recorded bindings and the ports of all nodes.
+	#[must_use]
+	pub fn validate(&self) -> GraphReport {
+		let mut report = GraphReport::default();
+		let mut destinations: BTreeMap<(ConstString, ConstString), usize> = BTreeMap::new();
+		for edge in &self.edges {
+			self.check(&edge.output, edge, false, &mut report);
+			if self.check(&edge.input, edge, true, &mut report) {
+				*destinations
+					.entry(edge.input.clone())
+					.or_default() += 1;
+			}
+		}
+
+		for ((node, port), count) in &destinations {
+			if *count > 1
+				|| self
+					.port(node, port)
+					.is_some_and(|port| port.is_bound())
+			{
+				report.issues.push(GraphIssue::BoundTwice {
+					node: node.clone(),
+					port: port.clone(),
+				});
+			}
+		}
+
+		for (node, ports) in &self.nodes {
+			for port in ports {
+				if port.direction().is_input()
+					&& !port.direction().is_output()
+					&& !port.is_bound()
+					&& (port.is_required() || !port.has_default())
+					&& !destinations.contains_key(&(node.clone(), port.name()))
+				{
+					report.issues.push(GraphIssue::Unbound {
+						node: node.clone(),
+						port: port.name(),
+					});
+				}
+			}
+		}
+
+		self.find_cycles(&mut report);
+		report
+	}
+
+	/// Validates the graph and binds all recorded bindings, if there are no issues.
+	/// Bindings failing nevertheless are reported as [`GraphIssue::BindingFailed`].
+	#[must_use]
+	pub fn bind(&self) -> GraphReport {
+		let mut report = self.validate();
+		if report.is_ok() {
+			for edge in &self.edges {
+				if let Some(in_port) = self.port(&edge.input.0, &edge.input.1)
+					&& let Some(out_port) = self.port(&edge.output.0, &edge.output.1)
+					&& let Err(error) = (edge.bind)(in_port, out_port)
+				{
+					report.issues.push(GraphIssue::BindingFailed {
+						node: edge.input.0.clone(),
+						port: edge.input.1.clone(),
+						error,
+					});
+				}
+			}
+		}
+		report
+	}
+
+	/// Returns the port `port` of node `node`.
+	fn port(&self, node: &str, port: &str) -> Option<&Port> {
+		self.nodes
+			.get(node)?
+			.iter()
+			.find(|item| &*item.name() == port)
+	}
+
+	/// Checks one end of a binding, returns `true` if the port exists.
+	fn check(&self, (node, name): &(ConstString, ConstString), edge: &Edge, input: bool, report: &mut GraphReport) -> bool {
+		let Some(ports) = self.nodes.get(node) else {
+			report
+				.issues
+				.push(GraphIssue::NodeNotFound { node: node.clone() });
+			return false;
+		};
+		let Some(port) = ports.iter().find(|port| port.name() == *name) else {
+			report.issues.push(GraphIssue::PortNotFound {
+				node: node.clone(),
+				port: name.clone(),
+			});
+			return false;
+		};
+		let direction = port.direction();
+		if (input && !direction.is_input()) || (!input && !direction.is_output()) {
+			report.issues.push(GraphIssue::WrongDirection {
+				node: node.clone(),
+				port: name.clone(),
+			});
+		}
+		if port.value_type_id() != edge.type_id {
+			report.issues.push(GraphIssue::WrongType {
+				node: node.clone(),
+				port: name.clone(),
+				expected: edge.type_name,
+				actual: port.type_name(),
+			});
+		}
+		true
+	}
+
+	/// Reports the cycles in the data flow of the recorded bindings.
+	fn find_cycles(&self, report: &mut GraphReport) {
+		/// Visiting state of a port.
+		#[derive(Clone, Copy, PartialEq)]
+		enum State {
+			Active,
+			Done,
+		}
+
+		let mut successors: BTreeMap<&(ConstString, ConstString), Vec<&(ConstString, ConstString)>> = BTreeMap::new();
+		for edge in &self.edges {
+			successors
+				.entry(&edge.output)
+				.or_default()
+				.push(&edge.input);
+		}
+
+		let mut states = BTreeMap::new();
+		for start in successors.keys() {
+			if states.contains_key(start) {
+				continue;
+			}
+			// depth first search with an explicit stack of ports and their next successor to visit
+			let mut path = Vec::from([(*start, 0)]);
+			states.insert(*start, State::Active);
+			while let Some((current, next)) = path.last_mut() {
+				let current = *current;
+				let successor = successors
+					.get(current)
+					.and_then(|items| items.get(*next))
+					.copied();
+				*next += 1;
+				if let Some(successor) = successor {
+					match states.get(successor) {
+						None => {
+							states.insert(successor, State::Active);
+							path.push((successor, 0));
+						}
+						Some(State::Active) => {
+							let begin = path
+								.iter()
+								.position(|(port, _)| *port == successor)
+								.unwrap_or_default();
+							report.issues.push(GraphIssue::Cycle {
+								ports: path[begin..]
+									.iter()
+									.map(|(port, _)| (*port).clone())
+									.collect(),
+							});
+						}
+						Some(State::Done) => {}
+					}
+				} else {
+					states.insert(current, State::Done);
+					path.pop();
+				}
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	// check, that the auto traits are available.
+	#[test]
+	const fn normal_types() {
+		is_normal::<&PortGraph>();
+		is_normal::<PortGraph>();
+		is_normal::<GraphReport>();
+	}
+}
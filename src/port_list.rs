@@ -3,21 +3,31 @@
 
 use core::ops::{Deref, DerefMut};
 
-use alloc::vec::Vec;
+use alloc::{collections::btree_map::BTreeMap, vec::Vec};
 
 use crate::{
+	ConstString,
 	port::Port,
-	traits::{PortAccessors, PortCommons, PortProvider},
+	port_name::is_same_name,
+	traits::{PortAccessors, PortCommons, PortIterators, PortProvider},
 };
 
 /// A list like container for [`Port`]s.
+///
+/// The list may have an index of the port names for faster lookup, see [`PortList::with_index`].
 #[derive(Default)]
-#[repr(transparent)]
-pub struct PortList(Vec<Port>);
+pub struct PortList {
+	/// The [`Port`]s.
+	ports: Vec<Port>,
+	/// Optional index from port name to position in `ports`.
+	index: Option<BTreeMap<ConstString, usize>>,
+}
 
 impl core::fmt::Debug for PortList {
 	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-		f.debug_tuple("PortList").field(&self.0).finish()
+		f.debug_tuple("PortList")
+			.field(&self.ports)
+			.finish()
 	}
 }
 
@@ -25,50 +35,113 @@ impl Deref for PortList {
 	type Target = [Port];
 
 	fn deref(&self) -> &Self::Target {
-		&self.0
+		&self.ports
 	}
 }
 
 impl DerefMut for PortList {
 	fn deref_mut(&mut self) -> &mut Self::Target {
-		&mut self.0
+		&mut self.ports
 	}
 }
 
 impl PortAccessors for PortList {}
 
+impl PortIterators for PortList {
+	fn ports(&self) -> impl Iterator<Item = &Port> {
+		self.ports.iter()
+	}
+}
+
 impl PortProvider for PortList {
-	fn find(&self, name: impl Into<crate::ConstString>) -> Option<&Port> {
+	fn find(&self, name: impl Into<ConstString>) -> Option<Port> {
 		let name = name.into();
-		self.0
-			.iter()
-			.find(|&port| port.name() == name)
-			.map(|v| v as _)
+		self.find_index(&name)
+			.map(|index| self.ports[index].clone())
+	}
+
+	fn provided_ports(&self) -> impl Iterator<Item = Port> {
+		self.ports.iter().cloned()
 	}
 }
 
 impl PortList {
 	pub fn new(ports: Vec<Port>) -> Self {
-		Self(ports)
+		Self { ports, index: None }
+	}
+
+	/// Creates a [`PortList`] with an index of the port names.
+	#[must_use]
+	pub fn with_index(ports: Vec<Port>) -> Self {
+		let mut list = Self::new(ports);
+		list.build_index();
+		list
+	}
+
+	/// Creates or recreates the index of the port names.
+	/// The index is kept up to date by [`PortList::add`] and [`PortList::remove`].
+	pub fn build_index(&mut self) {
+		let mut index = BTreeMap::new();
+		for (position, port) in self.ports.iter().enumerate() {
+			index.entry(port.name()).or_insert(position);
+		}
+		self.index = Some(index);
+	}
+
+	/// Returns `true` if the list has an index of the port names.
+	#[must_use]
+	pub const fn has_index(&self) -> bool {
+		self.index.is_some()
 	}
 
 	/// Adds a port to the portlist.
 	pub fn add(&mut self, port: Port) {
-		self.0.push(port)
+		if let Some(index) = &mut self.index {
+			index
+				.entry(port.name())
+				.or_insert(self.ports.len());
+		}
+		self.ports.push(port);
 	}
 
 	/// Removes a port from the port list.
 	pub fn remove(&mut self, name: &str) -> Option<Port> {
-		let index = self
-			.0
+		let port = self
+			.find_index(name)
+			.map(|index| self.ports.remove(index));
+		if port.is_some() && self.index.is_some() {
+			self.build_index();
+		}
+		port
+	}
+
+	/// Returns the position of the [`Port`] named `name`.
+	/// The position can be used with [`PortList::get_by_index`] to avoid repeated lookups by name.
+	#[must_use]
+	pub fn find_index(&self, name: &str) -> Option<usize> {
+		// the index is verified, as the ports may have been rearranged via `DerefMut`
+		if let Some(index) = &self.index
+			&& let Some(&position) = index.get(name)
+			&& let Some(port) = self.ports.get(position)
+			&& is_same_name(&port.name(), name)
+		{
+			return Some(position);
+		}
+		self.ports
 			.iter()
-			.position(|port| port.name() == name.into());
-		index.map(|index| self.0.remove(index))
+			.position(|port| is_same_name(&port.name(), name))
+	}
+
+	/// Returns the [`Port`] at position `index`.
+	#[must_use]
+	pub fn get_by_index(&self, index: usize) -> Option<&Port> {
+		self.ports.get(index)
 	}
 }
 
 #[cfg(test)]
 mod tests {
+	#![allow(clippy::unwrap_used)]
 	use alloc::{string::String, vec};
 
 	use super::*;
@@ -121,4 +194,41 @@ mod tests {
 		assert_eq!(s.find("p3"), s.find(STATIC_NAME));
 		assert!(s.find("p_non_existent").is_none());
 	}
+
+	// test indexed lookup.
+	#[test]
+	fn index() {
+		let mut s = PortList::with_index(vec![
+			Port::create_in_port::<i32>("p1"),
+			Port::create_in_port::<f64>(CONST_NAME),
+			Port::create_in_port::<String>(STATIC_NAME),
+		]);
+		assert!(s.has_index());
+		assert_eq!(s.find_index("p1"), Some(0));
+		assert_eq!(s.find_index(CONST_NAME), Some(1));
+		assert_eq!(s.find_index("p_non_existent"), None);
+		assert_eq!(s.get_by_index(2).unwrap().name().as_ref(), STATIC_NAME);
+		assert!(s.get_by_index(3).is_none());
+
+		s.add(Port::create_out_port::<i32>("p4"));
+		assert_eq!(s.find_index("p4"), Some(3));
+		assert!(s.remove("p1").is_some());
+		assert_eq!(s.find_index("p1"), None);
+		assert_eq!(s.find_index(CONST_NAME), Some(0));
+		assert_eq!(s.find_index("p4"), Some(2));
+
+		// rearranging the ports must not break the lookup
+		s.swap(0, 2);
+		assert_eq!(s.find_index("p4"), Some(0));
+		assert_eq!(s.find("p4").unwrap().name().as_ref(), "p4");
+		assert_eq!(s.find(CONST_NAME).unwrap().name().as_ref(), CONST_NAME);
+
+		let mut s = PortList::default();
+		assert!(!s.has_index());
+		s.add(Port::create_in_port::<i32>("p1"));
+		assert_eq!(s.find_index("p1"), Some(0));
+		s.build_index();
+		assert!(s.has_index());
+		assert_eq!(s.find_index("p1"), Some(0));
+	}
 }
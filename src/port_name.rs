@@ -0,0 +1,173 @@
+// Copyright © 2025 Stephan Kunz
+//! Interned port names.
+
+use core::ops::Deref;
+
+use alloc::{boxed::Box, collections::btree_map::BTreeMap};
+
+use crate::{ConstString, RwLock};
+
+/// The global registry of interned names.
+static NAMES: RwLock<BTreeMap<&'static str, &'static ConstString>> = RwLock::new(BTreeMap::new());
+
+/// Returns the interned [`ConstString`] for `name`, interning it if necessary.
+fn intern(name: &str) -> &'static ConstString {
+	if let Some(interned) = NAMES.read().get(name) {
+		return interned;
+	}
+	let mut names = NAMES.write();
+	// another thread may have interned the name in between
+	if let Some(interned) = names.get(name) {
+		return interned;
+	}
+	let interned: &'static ConstString = Box::leak(Box::new(ConstString::from(name)));
+	names.insert(interned, interned);
+	interned
+}
+
+/// Returns `true` if both names are equal, with a fast path for identical (e.g. interned) names.
+#[inline]
+pub(crate) fn is_same_name(a: &str, b: &str) -> bool {
+	core::ptr::eq(a, b) || a == b
+}
+
+/// An interned name of a port.
+///
+/// Each distinct name is stored once for the lifetime of the program,
+/// so a [`PortName`] is `Copy`, compares by pointer and converts into a [`ConstString`] without allocation.
+/// It is accepted everywhere an `impl Into<ConstString>` is accepted.
+#[derive(Clone, Copy)]
+pub struct PortName(&'static ConstString);
+
+impl core::fmt::Debug for PortName {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_tuple("PortName")
+			.field(&self.as_str())
+			.finish()
+	}
+}
+
+impl core::fmt::Display for PortName {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+impl Deref for PortName {
+	type Target = str;
+
+	fn deref(&self) -> &Self::Target {
+		self.as_str()
+	}
+}
+
+impl AsRef<str> for PortName {
+	fn as_ref(&self) -> &str {
+		self.as_str()
+	}
+}
+
+impl PartialEq for PortName {
+	fn eq(&self, other: &Self) -> bool {
+		core::ptr::eq(self.0, other.0)
+	}
+}
+
+impl Eq for PortName {}
+
+impl PartialEq<str> for PortName {
+	fn eq(&self, other: &str) -> bool {
+		is_same_name(self.as_str(), other)
+	}
+}
+
+impl PartialEq<&str> for PortName {
+	fn eq(&self, other: &&str) -> bool {
+		is_same_name(self.as_str(), other)
+	}
+}
+
+impl PartialOrd for PortName {
+	fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for PortName {
+	fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+		if self == other {
+			core::cmp::Ordering::Equal
+		} else {
+			self.as_str().cmp(other.as_str())
+		}
+	}
+}
+
+impl core::hash::Hash for PortName {
+	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+		core::ptr::hash(self.0, state);
+	}
+}
+
+impl From<&str> for PortName {
+	fn from(name: &str) -> Self {
+		Self::new(name)
+	}
+}
+
+impl From<&ConstString> for PortName {
+	fn from(name: &ConstString) -> Self {
+		Self::new(name)
+	}
+}
+
+impl From<PortName> for ConstString {
+	fn from(name: PortName) -> Self {
+		name.0.clone()
+	}
+}
+
+impl PortName {
+	/// Creates the [`PortName`] for `name`, interning it on first use.
+	#[must_use]
+	pub fn new(name: &str) -> Self {
+		Self(intern(name))
+	}
+
+	/// Returns the name as `&str`.
+	#[must_use]
+	pub fn as_str(&self) -> &'static str {
+		self.0
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const fn is_normal<T: Sized + Send + Sync + Unpin>() {}
+
+	// check, that the auto traits are available.
+	#[test]
+	const fn normal_types() {
+		is_normal::<&PortName>();
+		is_normal::<PortName>();
+	}
+
+	#[test]
+	fn interning() {
+		let a = PortName::new("a");
+		let b = PortName::from("b");
+		assert_eq!(a, PortName::new("a"));
+		assert_ne!(a, b);
+		assert!(a < b);
+		assert_eq!(a, "a");
+		assert_eq!(a.as_str(), "a");
+		assert!(core::ptr::eq(a.as_str(), PortName::new("a").as_str()));
+
+		let name: ConstString = a.into();
+		let other: ConstString = PortName::new("a").into();
+		assert!(alloc::sync::Arc::ptr_eq(&name, &other));
+		assert_eq!(PortName::from(&name), a);
+	}
+}
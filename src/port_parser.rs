@@ -0,0 +1,170 @@
+// Copyright © 2025 Stephan Kunz
+//! Parsing the values of [`Port`]s from strings.
+
+use core::{
+	any::{Any, TypeId},
+	fmt::Display,
+	str::FromStr,
+};
+
+use alloc::{
+	collections::btree_map::BTreeMap,
+	string::{String, ToString},
+	sync::Arc,
+	vec::Vec,
+};
+
+use crate::{
+	RwLock,
+	error::{Error, Result},
+	port::Port,
+	traits::PortCommons,
+};
+
+/// Separator of the elements of a [`Vec`].
+const SEPARATOR: char = ';';
+
+/// Function parsing a string and setting the result as value of a [`Port`].
+type SetFn = Arc<dyn Fn(&Port, &str) -> Result<()> + Send + Sync>;
+
+/// The global registry of parsers by the [`TypeId`] of the value.
+static PARSERS: RwLock<BTreeMap<TypeId, SetFn>> = RwLock::new(BTreeMap::new());
+
+/// Creates a [`SetFn`] using `parser`.
+fn setter<T, E>(parser: impl Fn(&str) -> core::result::Result<T, E> + Send + Sync + 'static) -> SetFn
+where
+	T: Any + Send + Sync,
+	E: Display,
+{
+	Arc::new(move |port, value| {
+		let parsed =
+			parser(value).map_err(|error| parse_failed(port, value, core::any::type_name::<T>(), &error.to_string()))?;
+		// a derived value is a temporary one, which would silently drop the parsed value
+		if port.is_derived::<T>() {
+			Err(Error::NotSettable { port: port.name() })
+		} else if let Some(value_ref) = port.as_value::<T>() {
+			value_ref.set(parsed);
+			Ok(())
+		} else if let Some(cell) = port.as_lock_free_cell::<T>() {
+			cell.set(Some(parsed));
+			Ok(())
+		} else if port.as_lock_free_value::<T>().is_some() {
+			Err(Error::LockFree { port: port.name() })
+		} else {
+			Err(Error::NotSettable { port: port.name() })
+		}
+	})
+}
+
+/// Parses the elements of a [`Vec`] separated by [`SEPARATOR`].
+fn parse_vec<T: FromStr>(value: &str) -> core::result::Result<Vec<T>, T::Err> {
+	if value.trim().is_empty() {
+		return Ok(Vec::new());
+	}
+	value
+		.split(SEPARATOR)
+		.map(|element| element.trim().parse())
+		.collect()
+}
+
+/// Returns an [`Error::ParseFailed`] for `port` and `value`.
+fn parse_failed(port: &Port, value: &str, expected: &'static str, reason: &str) -> Error {
+	Error::ParseFailed {
+		port: port.name(),
+		value: value.into(),
+		expected,
+		reason: reason.into(),
+	}
+}
+
+/// Returns the builtin parser for the type with `type_id`.
+fn builtin(type_id: TypeId) -> Option<SetFn> {
+	macro_rules! builtin {
+		($($tp:ty),*) => {
+			$(
+				if type_id == TypeId::of::<$tp>() {
+					return Some(setter(<$tp>::from_str));
+				}
+				if type_id == TypeId::of::<Vec<$tp>>() {
+					return Some(setter(parse_vec::<$tp>));
+				}
+			)*
+		};
+	}
+	builtin!(
+		bool, char, f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, String
+	);
+	None
+}
+
+impl Port {
+	/// Registers `parser` for values of type `T`, replacing a previously registered or builtin parser.
+	///
+	/// Parsers using [`FromStr`] are builtin for `bool`, `char`, the numeric primitives and [`String`],
+	/// and for [`Vec`]s of these with elements separated by `;`, like `"1;2;3"`.
+	pub fn register_parser<T, E>(parser: impl Fn(&str) -> core::result::Result<T, E> + Send + Sync + 'static)
+	where
+		T: Any + Send + Sync,
+		E: Display,
+	{
+		PARSERS
+			.write()
+			.insert(TypeId::of::<T>(), setter(parser));
+	}
+
+	/// Registers [`FromStr`] as parser for values of type `T`
+	/// and for [`Vec`]s of `T` with elements separated by `;`.
+	pub fn register_from_str<T>()
+	where
+		T: Any + FromStr + Send + Sync,
+		T::Err: Display,
+	{
+		Self::register_parser(T::from_str);
+		Self::register_parser(parse_vec::<T>);
+	}
+
+	/// Parses `value` with the parser registered for the ports value type and sets the result as value.
+	/// See [`Port::register_parser`].
+	/// # Errors
+	/// - [`Error::LockFree`], if the port is a lock-free in port.
+	/// - [`Error::NotSettable`], if the port has no single value, like queue ports,
+	///   or a value computed by a [`ComputedPort`](crate::ComputedPort) or converted by a binding.
+	/// - [`Error::ParseFailed`], if no parser is registered or `value` cannot be parsed.
+	pub fn set_from_str(&self, value: &str) -> Result<()> {
+		let type_id = self.value_type_id();
+		// the lock is released before parsing
+		let registered = PARSERS.read().get(&type_id).cloned();
+		if let Some(parser) = registered.or_else(|| builtin(type_id)) {
+			parser(self, value)
+		} else {
+			Err(parse_failed(self, value, self.type_name(), "no parser registered"))
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#![allow(clippy::unwrap_used)]
+	use alloc::vec;
+
+	use crate::ConstString;
+
+	use super::*;
+
+	#[test]
+	fn vectors() {
+		assert_eq!(parse_vec::<i32>("1;2;3").unwrap(), vec![1, 2, 3]);
+		assert_eq!(parse_vec::<i32>(" 1; 2 ;3 ").unwrap(), vec![1, 2, 3]);
+		assert_eq!(parse_vec::<i32>("").unwrap(), Vec::<i32>::new());
+		assert!(parse_vec::<i32>("1;;3").is_err());
+		assert_eq!(parse_vec::<String>("a;b").unwrap(), vec!["a", "b"]);
+	}
+
+	#[test]
+	fn builtins() {
+		assert!(builtin(TypeId::of::<f64>()).is_some());
+		assert!(builtin(TypeId::of::<Vec<u8>>()).is_some());
+		assert!(builtin(TypeId::of::<Vec<Vec<u8>>>()).is_none());
+		assert!(builtin(TypeId::of::<ConstString>()).is_none());
+	}
+}
@@ -0,0 +1,161 @@
+// Copyright © 2025 Stephan Kunz
+//! Implementation of the bounded FIFO [`Queue`] used by queue ports.
+
+use alloc::{collections::vec_deque::VecDeque, vec::Vec};
+
+use crate::{
+	ConstString,
+	error::{Error, Result},
+	port_value::PortValuePtr,
+};
+
+/// Behaviour of a queue port when a value is pushed into a full queue.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+	/// The oldest value is dropped to make room for the new value.
+	#[default]
+	DropOldest,
+	/// The new value is dropped.
+	DropNewest,
+	/// The new value is rejected with [`Error::QueueFull`].
+	Error,
+}
+
+/// Result of a push into a [`Queue`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Pushed {
+	/// The value has been stored.
+	Stored,
+	/// The value has been dropped.
+	Dropped,
+	/// The value has been rejected.
+	Rejected,
+}
+
+/// A bounded FIFO buffer with an [`OverflowPolicy`].
+#[derive(Clone, Debug)]
+pub(crate) struct Queue<T> {
+	/// The buffered values.
+	buffer: VecDeque<T>,
+	/// Maximum number of buffered values.
+	capacity: usize,
+	/// Behaviour on overflow.
+	policy: OverflowPolicy,
+}
+
+impl<T> Queue<T> {
+	/// Creates an empty [`Queue`], a `capacity` of `0` is treated as `1`.
+	pub(crate) fn new(capacity: usize, policy: OverflowPolicy) -> Self {
+		let capacity = capacity.max(1);
+		Self {
+			buffer: VecDeque::with_capacity(capacity),
+			capacity,
+			policy,
+		}
+	}
+
+	pub(crate) const fn capacity(&self) -> usize {
+		self.capacity
+	}
+
+	pub(crate) const fn policy(&self) -> OverflowPolicy {
+		self.policy
+	}
+
+	pub(crate) fn len(&self) -> usize {
+		self.buffer.len()
+	}
+
+	fn push(&mut self, value: T) -> Pushed {
+		if self.buffer.len() >= self.capacity {
+			match self.policy {
+				OverflowPolicy::DropOldest => {
+					self.buffer.pop_front();
+				}
+				OverflowPolicy::DropNewest => return Pushed::Dropped,
+				OverflowPolicy::Error => return Pushed::Rejected,
+			}
+		}
+		self.buffer.push_back(value);
+		Pushed::Stored
+	}
+}
+
+/// Pushes `value` into the queue of the port named `port`.
+/// Only a stored value counts as a change of the queue.
+/// # Errors
+/// - [`Error::NoValueSet`], if the port has no queue.
+/// - [`Error::QueueFull`], if the queue is full and the policy is [`OverflowPolicy::Error`].
+pub(crate) fn push<T>(port: ConstString, queue: &PortValuePtr<Queue<T>>, value: T) -> Result<()> {
+	match queue.modify(|queue| {
+		let pushed = queue.push(value);
+		(pushed, pushed == Pushed::Stored)
+	}) {
+		Some(Pushed::Stored | Pushed::Dropped) => Ok(()),
+		Some(Pushed::Rejected) => Err(Error::QueueFull { port }),
+		None => Err(Error::NoValueSet { port }),
+	}
+}
+
+/// Pops the oldest value from the queue, taking a value out does not count as a change.
+pub(crate) fn pop<T>(queue: &PortValuePtr<Queue<T>>) -> Option<T> {
+	queue
+		.modify(|queue| (queue.buffer.pop_front(), false))
+		.flatten()
+}
+
+/// Pops all values from the queue, taking values out does not count as a change.
+pub(crate) fn drain<T>(queue: &PortValuePtr<Queue<T>>) -> Vec<T> {
+	queue
+		.modify(|queue| (queue.buffer.drain(..).collect(), false))
+		.unwrap_or_default()
+}
+
+/// Returns a copy of the oldest value of the queue.
+pub(crate) fn peek<T: Clone>(queue: &PortValuePtr<Queue<T>>) -> Option<T> {
+	queue
+		.read()
+		.as_ref()
+		.and_then(|queue| queue.buffer.front().cloned())
+}
+
+/// Returns the number of values in the queue.
+pub(crate) fn len<T>(queue: &PortValuePtr<Queue<T>>) -> usize {
+	queue.read().as_ref().map_or(0, Queue::len)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	// check, that the auto traits are available.
+	#[test]
+	const fn normal_types() {
+		is_normal::<&Queue<i32>>();
+		is_normal::<Queue<i32>>();
+		is_normal::<OverflowPolicy>();
+	}
+
+	#[test]
+	fn overflow() {
+		let mut queue = Queue::new(2, OverflowPolicy::DropOldest);
+		assert_eq!(queue.push(1), Pushed::Stored);
+		assert_eq!(queue.push(2), Pushed::Stored);
+		assert_eq!(queue.push(3), Pushed::Stored);
+		assert_eq!(queue.buffer, [2, 3]);
+
+		let mut queue = Queue::new(2, OverflowPolicy::DropNewest);
+		assert_eq!(queue.push(1), Pushed::Stored);
+		assert_eq!(queue.push(2), Pushed::Stored);
+		assert_eq!(queue.push(3), Pushed::Dropped);
+		assert_eq!(queue.buffer, [1, 2]);
+
+		let mut queue = Queue::new(0, OverflowPolicy::Error);
+		assert_eq!(queue.capacity(), 1);
+		assert_eq!(queue.push(1), Pushed::Stored);
+		assert_eq!(queue.push(2), Pushed::Rejected);
+		assert_eq!(queue.buffer, [1]);
+	}
+}
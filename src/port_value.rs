@@ -1,81 +1,422 @@
 // Copyright © 2025 Stephan Kunz
 //! Internal port value representation and its read & write guards.
 
-use core::ops::{Deref, DerefMut};
+use core::{
+	ops::{Deref, DerefMut},
+	task::Waker,
+};
 
-use alloc::sync::Arc;
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
 
 use crate::{
-	ConstString, RwLock, RwLockReadGuard, RwLockWriteGuard,
+	ConstString, RwLock,
 	error::{Error, Result},
+	lock::RawRwLock,
 	sequence_number::SequenceNumber,
 };
 
-/// Type definition for a pointer to a [`PortValue`]
-pub(crate) type PortValuePtr<T> = Arc<RwLock<PortValue<T>>>;
+/// Type definition for a change callback, receiving the name of the observing port and the new sequence number.
+pub(crate) type ChangeCallback = Arc<dyn Fn(&str, u32) + Send + Sync>;
+
+/// Type definition for a recorder, receiving each new value with its sequence number while the value is locked.
+pub(crate) type Recorder<T> = Box<dyn FnMut(u32, &T) + Send + Sync>;
+
+/// An observer of a [`PortValue`]: a [`ChangeCallback`] together with the name of the port it is registered for.
+#[derive(Clone)]
+pub(crate) struct Observer {
+	/// Name of the observing port.
+	port: ConstString,
+	/// The callback.
+	callback: ChangeCallback,
+}
+
+impl Observer {
+	pub(crate) fn new(port: impl Into<ConstString>, callback: ChangeCallback) -> Self {
+		Self {
+			port: port.into(),
+			callback,
+		}
+	}
+
+	/// Observers are identical, if they share the same callback.
+	pub(crate) fn is(&self, other: &Self) -> bool {
+		Arc::ptr_eq(&self.callback, &other.callback)
+	}
+}
+
+/// The [`Waker`]s of tasks waiting for a change,
+/// each registered under the id of its [`PortChanged`](crate::port_changed::PortChanged) future.
+#[derive(Default)]
+pub(crate) struct Wakers(Vec<(usize, Waker)>);
+
+impl Wakers {
+	/// Registers the `waker` of the future with `id`, replacing the one registered by the same future before.
+	pub(crate) fn register(&mut self, id: usize, waker: &Waker) {
+		if let Some((_, registered)) = self.0.iter_mut().find(|(item, _)| *item == id) {
+			if !registered.will_wake(waker) {
+				registered.clone_from(waker);
+			}
+		} else {
+			self.0.push((id, waker.clone()));
+		}
+	}
+
+	/// Removes the waker of the future with `id`.
+	pub(crate) fn remove(&mut self, id: usize) {
+		self.0.retain(|(item, _)| *item != id);
+	}
+
+	/// Takes all registered wakers.
+	pub(crate) fn take(&mut self) -> Self {
+		Self(core::mem::take(&mut self.0))
+	}
+
+	/// Returns the number of registered wakers.
+	#[cfg(test)]
+	pub(crate) fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	/// Wakes all registered wakers.
+	fn wake(self) {
+		for (_, waker) in self.0 {
+			waker.wake();
+		}
+	}
+}
+
+/// A pending notification about a change of a [`PortValue`].
+/// It is created while the value is locked and must be delivered after releasing the lock.
+#[must_use = "a `Notification` must be delivered"]
+pub(crate) struct Notification {
+	/// The new sequence number.
+	sequence_number: u32,
+	/// The observers to notify.
+	observers: Vec<Observer>,
+	/// The wakers of tasks waiting for a change.
+	wakers: Wakers,
+}
+
+impl Notification {
+	pub(crate) const fn new(sequence_number: u32, observers: Vec<Observer>, wakers: Wakers) -> Self {
+		Self {
+			sequence_number,
+			observers,
+			wakers,
+		}
+	}
+
+	/// Delivers the notification to the observers and wakes the waiting tasks.
+	pub(crate) fn notify(self) {
+		for observer in self.observers {
+			(observer.callback)(&observer.port, self.sequence_number);
+		}
+		self.wakers.wake();
+	}
+}
+
+/// A value of type `T` derived from other values, like the converted value of a converting binding.
+/// A port bound to a derived value takes its value from that on each access.
+pub(crate) trait DerivedValue<T>: Send + Sync {
+	/// Returns the current value, which is replaced by a new one,
+	/// if the values it is derived from have changed since the last update.
+	/// Guards held on a former value do not prevent the update.
+	fn value(&self) -> PortValuePtr<T>;
+
+	/// Returns the address of the observed value.
+	fn address(&self) -> usize;
+
+	/// Adds an [`Observer`] to the observed value.
+	fn subscribe(&self, observer: Observer);
+
+	/// Removes an [`Observer`] from the observed value.
+	fn unsubscribe(&self, observer: &Observer);
+
+	/// Returns the sequence number of the observed value if it differs from `since`,
+	/// otherwise registers the `waker` of the future with `id` for the next change.
+	fn poll_change(&self, since: u32, id: usize, waker: &Waker) -> Option<u32>;
+
+	/// Removes the waker of the future with `id`.
+	fn remove_waker(&self, id: usize);
+}
+
+/// Pointer to a [`PortValue`].
+/// Changes done via the pointer's methods are notified to the observers of the value.
+pub(crate) struct PortValuePtr<T>(Arc<RwLock<PortValue<T>>>);
+
+impl<T> Clone for PortValuePtr<T> {
+	fn clone(&self) -> Self {
+		Self(self.0.clone())
+	}
+}
+
+impl<T> Deref for PortValuePtr<T> {
+	type Target = RwLock<PortValue<T>>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl<T> PortValuePtr<T> {
+	pub(crate) fn new(value: PortValue<T>) -> Self {
+		Self(Arc::new(RwLock::new(value)))
+	}
+
+	/// Returns the address of the value, which is the same for all pointers to the value.
+	pub(crate) fn address(&self) -> usize {
+		Arc::as_ptr(&self.0).addr()
+	}
+
+	/// Returns `true` if both pointers point to the same value.
+	pub(crate) fn ptr_eq(&self, other: &Self) -> bool {
+		Arc::ptr_eq(&self.0, &other.0)
+	}
+
+	pub(crate) fn replace(&self, value: impl Into<T>) -> Option<T> {
+		let (old, notification) = {
+			let mut guard = self.0.write();
+			let old = guard.replace(value);
+			(old, guard.notification())
+		};
+		notification.notify();
+		old
+	}
+
+	pub(crate) fn set(&self, value: impl Into<T>) {
+		let notification = {
+			let mut guard = self.0.write();
+			guard.set(value);
+			guard.notification()
+		};
+		notification.notify();
+	}
+
+	pub(crate) fn take(&self) -> Option<T> {
+		let (old, notification) = {
+			let mut guard = self.0.write();
+			let old = guard.take();
+			(old, guard.notification())
+		};
+		notification.notify();
+		old
+	}
+
+	/// Applies `f` to the contained value, if there is one, and returns its result.
+	/// If `f` reports a change, the sequence number is incremented and the observers are notified.
+	pub(crate) fn modify<R>(&self, f: impl FnOnce(&mut T) -> (R, bool)) -> Option<R> {
+		let (result, notification) = {
+			let mut guard = self.0.write();
+			let (result, changed) = f(guard.value.as_mut()?);
+			if changed {
+				guard.changed();
+				(result, Some(guard.notification()))
+			} else {
+				(result, None)
+			}
+		};
+		if let Some(notification) = notification {
+			notification.notify();
+		}
+		Some(result)
+	}
+
+	/// Sets the [`Recorder`], which is called with each new value.
+	pub(crate) fn set_recorder(&self, recorder: Recorder<T>) {
+		self.0.write().recorder = Some(recorder);
+	}
+
+	/// Adds an [`Observer`].
+	pub(crate) fn subscribe(&self, observer: Observer) {
+		self.0.write().observers.push(observer);
+	}
+
+	/// Removes an [`Observer`].
+	pub(crate) fn unsubscribe(&self, observer: &Observer) {
+		self.0
+			.write()
+			.observers
+			.retain(|item| !item.is(observer));
+	}
+}
+
+/// Shared locking of a [`PortValuePtr`] independent of the value type.
+pub(crate) trait SharedLock {
+	/// Returns the address of the value.
+	fn address(&self) -> usize;
+
+	/// Acquires a read lock, which must be released by a [`PortValueReadGuard`].
+	fn lock_shared(&self);
+}
+
+impl<T> SharedLock for PortValuePtr<T> {
+	fn address(&self) -> usize {
+		Self::address(self)
+	}
+
+	fn lock_shared(&self) {
+		self.raw().lock_shared();
+	}
+}
+
+/// A plain value is a [`DerivedValue`] of its own, which never changes by itself.
+impl<T: Send + Sync> DerivedValue<T> for PortValuePtr<T> {
+	fn value(&self) -> Self {
+		self.clone()
+	}
+
+	fn address(&self) -> usize {
+		Self::address(self)
+	}
+
+	fn subscribe(&self, observer: Observer) {
+		Self::subscribe(self, observer);
+	}
+
+	fn unsubscribe(&self, observer: &Observer) {
+		Self::unsubscribe(self, observer);
+	}
+
+	fn poll_change(&self, since: u32, id: usize, waker: &Waker) -> Option<u32> {
+		let mut guard = self.0.write();
+		let sequence_number = guard.sequence_number();
+		if sequence_number == since {
+			guard.register_waker(id, waker);
+			None
+		} else {
+			Some(sequence_number)
+		}
+	}
+
+	fn remove_waker(&self, id: usize) {
+		self.0.write().remove_waker(id);
+	}
+}
 
 /// Internal representation of a ports value.
-/// The data `T` together with its [`SequenceNumber`].
 /// The `PortValue` is shared between the bound ports.
-pub(crate) struct PortValue<T>(Option<T>, SequenceNumber);
+pub(crate) struct PortValue<T> {
+	/// The data, `None` if no value is set.
+	value: Option<T>,
+	/// The sequence number, incremented with each change.
+	sequence: SequenceNumber,
+	/// The [`Observer`]s of changes.
+	observers: Vec<Observer>,
+	/// The [`Waker`]s of tasks waiting for a change.
+	wakers: Wakers,
+	/// An optional [`Recorder`] of the changed values.
+	recorder: Option<Recorder<T>>,
+}
 
 impl<T> Default for PortValue<T> {
 	fn default() -> Self {
-		Self(None, SequenceNumber::default())
+		Self::with(None, SequenceNumber::default())
 	}
 }
 
 impl<T: core::fmt::Debug> core::fmt::Debug for PortValue<T> {
 	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-		f.debug_tuple("PortValue")
-			.field(&self.0)
-			.field(&self.1)
-			.finish()
+		f.debug_struct("PortValue")
+			.field("value", &self.value)
+			.field("sequence", &self.sequence)
+			.finish_non_exhaustive()
 	}
 }
 
 impl<T> PortValue<T> {
+	/// Creates a [`PortValue`] without observers, wakers and recorder.
+	const fn with(value: Option<T>, sequence: SequenceNumber) -> Self {
+		Self {
+			value,
+			sequence,
+			observers: Vec::new(),
+			wakers: Wakers(Vec::new()),
+			recorder: None,
+		}
+	}
+
 	pub(crate) fn new(value: impl Into<T>) -> Self {
 		let mut sq = SequenceNumber::default();
 		sq.increment();
-		Self(Some(value.into()), sq)
+		Self::with(Some(value.into()), sq)
+	}
+
+	/// Creates a [`PortValue`] with an initial `value`, which does not count as a change.
+	pub(crate) fn initial(value: impl Into<T>) -> Self {
+		Self::with(Some(value.into()), SequenceNumber::default())
 	}
 
 	pub(crate) const fn as_ref(&self) -> Option<&T> {
-		self.0.as_ref()
+		self.value.as_ref()
 	}
 
 	pub(crate) const fn is_some(&self) -> bool {
-		self.0.is_some()
+		self.value.is_some()
 	}
 
 	pub(crate) fn is_none(&self) -> bool {
-		self.0.is_none()
+		self.value.is_none()
+	}
+
+	/// Creates a [`Notification`] about the current state, taking over the registered [`Waker`]s.
+	pub(crate) fn notification(&mut self) -> Notification {
+		Notification {
+			sequence_number: self.sequence.value(),
+			observers: self.observers.clone(),
+			wakers: self.wakers.take(),
+		}
+	}
+
+	/// Marks the value as changed: increments the sequence number and records the new value.
+	fn changed(&mut self) {
+		self.sequence.increment();
+		if let Some(recorder) = &mut self.recorder
+			&& let Some(value) = &self.value
+		{
+			recorder(self.sequence.value(), value);
+		}
+	}
+
+	/// Registers the [`Waker`] of the future with `id` to be woken on the next change.
+	pub(crate) fn register_waker(&mut self, id: usize, waker: &Waker) {
+		self.wakers.register(id, waker);
+	}
+
+	/// Removes the [`Waker`] of the future with `id`.
+	pub(crate) fn remove_waker(&mut self, id: usize) {
+		self.wakers.remove(id);
 	}
 
 	pub(crate) fn replace(&mut self, value: impl Into<T>) -> Option<T> {
-		self.1.increment();
-		self.0.replace(value.into())
+		let old = self.value.replace(value.into());
+		self.changed();
+		old
 	}
 
 	pub(crate) const fn sequence_number(&self) -> u32 {
-		self.1.value()
+		self.sequence.value()
 	}
 
 	pub(crate) fn set(&mut self, value: impl Into<T>) {
-		self.1.increment();
-		self.0 = Some(value.into())
+		self.value = Some(value.into());
+		self.changed();
 	}
 
 	pub(crate) fn take(&mut self) -> Option<T> {
-		self.1.increment();
-		self.0.take()
+		self.sequence.increment();
+		self.value.take()
+	}
+
+	/// Mirrors the `value` and `sequence_number` of another value, which is not a change of its own.
+	pub(crate) fn mirror(&mut self, value: Option<T>, sequence_number: u32) {
+		self.value = value;
+		self.sequence = SequenceNumber::new(sequence_number);
 	}
 }
 
 impl<T: Clone> PortValue<T> {
 	pub(crate) fn get(&self) -> Option<T> {
-		self.0.clone()
+		self.value.clone()
 	}
 }
 
@@ -104,32 +445,28 @@ impl<T> Deref for PortValueReadGuard<T> {
 impl<T> Drop for PortValueReadGuard<T> {
 	#[allow(unsafe_code)]
 	fn drop(&mut self) {
-		// SAFETY: manually decrementing lock because entry is permanently locked in new()
+		// SAFETY: manually unlocking because the value is locked without a guard in new()
 		unsafe {
-			self.value.force_read_decrement();
+			self.value.raw().unlock_shared();
 		}
 	}
 }
 
 impl<T> PortValueReadGuard<T> {
+	/// Returns the sequence number of the locked value.
+	#[allow(unsafe_code)]
+	#[must_use]
+	pub fn sequence_number(&self) -> u32 {
+		// SAFETY: the value is read locked until self is dropped
+		unsafe { (*self.value.data_ptr()).sequence_number() }
+	}
+
 	/// Returns a read guard to a T.
 	/// # Errors
 	/// - [`Error::NoValueSet`] if the port does not yet contain a value.
 	pub(crate) fn new(port: impl Into<ConstString>, value: PortValuePtr<T>) -> Result<Self> {
-		// we know this pointer is valid since the guard owns the value
-		let ptr_t = {
-			let guard = value.read();
-			// leak returns &'rwlock &Option<T> but read locks RwLock forewer
-			let x = RwLockReadGuard::leak(guard);
-			if let Some(value) = &x.0 {
-				let ptr_t: *const T = value;
-				ptr_t
-			} else {
-				return Err(Error::NoValueSet { port: port.into() });
-			}
-		};
-
-		Ok(Self { value, ptr_t })
+		value.raw().lock_shared();
+		Self::locked(port, value)
 	}
 
 	/// Returns a read guard to a T.
@@ -137,23 +474,28 @@ impl<T> PortValueReadGuard<T> {
 	/// - [`Error::IsLocked`]  if the entry is locked by someone else.
 	/// - [`Error::NoValueSet`] if the port does not yet contain a value.
 	pub(crate) fn try_new(port: impl Into<ConstString>, value: PortValuePtr<T>) -> Result<Self> {
-		// we know this pointer is valid since the guard owns the value
-		let ptr_t = {
-			if let Some(guard) = value.try_read() {
-				// leak returns &'rwlock &Option<T> but read locks RwLock forewer
-				let x = RwLockReadGuard::leak(guard);
-				if let Some(value) = &x.0 {
-					let ptr_t: *const T = value;
-					ptr_t
-				} else {
-					return Err(Error::NoValueSet { port: port.into() });
-				}
-			} else {
-				return Err(Error::IsLocked { port: port.into() });
-			}
-		};
+		if !value.raw().try_lock_shared() {
+			return Err(Error::IsLocked { port: port.into() });
+		}
+		Self::locked(port, value)
+	}
 
-		Ok(Self { value, ptr_t })
+	/// Creates the guard for an already read locked `value`, the lock is released on error.
+	/// # Errors
+	/// - [`Error::NoValueSet`] if the port does not yet contain a value.
+	#[allow(unsafe_code)]
+	pub(crate) fn locked(port: impl Into<ConstString>, value: PortValuePtr<T>) -> Result<Self> {
+		// SAFETY: the value is read locked and the guard owns the value
+		if let Some(inner) = unsafe { &(*value.data_ptr()).value } {
+			let ptr_t: *const T = inner;
+			Ok(Self { value, ptr_t })
+		} else {
+			// SAFETY: the value has been read locked above
+			unsafe {
+				value.raw().unlock_shared();
+			}
+			Err(Error::NoValueSet { port: port.into() })
+		}
 	}
 }
 
@@ -167,8 +509,8 @@ pub struct PortValueWriteGuard<T> {
 	value: PortValuePtr<T>,
 	/// Mutable pointer to content of the `value` above.
 	ptr_t: *mut T,
-	/// Mutable pointer to the sequence_id.
-	ptr_seq_id: *mut SequenceNumber,
+	/// Mutable pointer to the locked port value, the `value` above.
+	ptr_port_value: *mut PortValue<T>,
 	/// Change flag.
 	modified: bool,
 }
@@ -196,14 +538,25 @@ impl<T> DerefMut for PortValueWriteGuard<T> {
 impl<T> Drop for PortValueWriteGuard<T> {
 	#[allow(unsafe_code)]
 	fn drop(&mut self) {
-		// SAFETY: manually removing lock because entry is permanently locked in new()
-		unsafe {
-			// if modified, increment sequence id
-			if self.modified {
-				self.ptr_seq_id.as_mut().unwrap().increment();
+		// if modified, increment sequence id and prepare notification
+		let notification = if self.modified {
+			// SAFETY: the port value is still locked by this guard
+			unsafe {
+				(*self.ptr_port_value).changed();
+				Some((*self.ptr_port_value).notification())
 			}
+		} else {
+			None
+		};
+
+		// SAFETY: manually unlocking because the value is locked without a guard in new()
+		unsafe {
+			self.value.raw().unlock_exclusive();
+		}
 
-			self.value.force_write_unlock();
+		// notify after releasing the lock
+		if let Some(notification) = notification {
+			notification.notify();
 		}
 	}
 }
@@ -213,26 +566,8 @@ impl<T> PortValueWriteGuard<T> {
 	/// # Errors
 	/// - [`Error::NoValueSet`] if the port does not yet contain a value.
 	pub(crate) fn new(port: impl Into<ConstString>, value: PortValuePtr<T>) -> Result<Self> {
-		// we know this pointer is valid since the guard owns the value
-		let (ptr_t, ptr_seq_id) = {
-			let guard = value.write();
-			// leak returns &'rwlock &Option<T> but write locks RwLock forewer
-			let x = RwLockWriteGuard::leak(guard);
-			if let Some(value) = &mut x.0 {
-				let ptr_t: *mut T = value;
-				let ptr_seq_id: *mut SequenceNumber = &raw mut x.1;
-				(ptr_t, ptr_seq_id)
-			} else {
-				return Err(Error::NoValueSet { port: port.into() });
-			}
-		};
-
-		Ok(Self {
-			value,
-			ptr_t,
-			ptr_seq_id,
-			modified: false,
-		})
+		value.raw().lock_exclusive();
+		Self::locked(port, value)
 	}
 
 	/// Returns a write guard to a T.
@@ -240,29 +575,32 @@ impl<T> PortValueWriteGuard<T> {
 	/// - [`Error::IsLocked`]  if the entry is locked by someone else.
 	/// - [`Error::NoValueSet`] if the port does not yet contain a value.
 	pub(crate) fn try_new(port: impl Into<ConstString>, value: PortValuePtr<T>) -> Result<Self> {
-		// we know this pointer is valid since the guard owns the value
-		let (ptr_t, ptr_seq_id) = {
-			if let Some(guard) = value.try_write() {
-				// leak returns &'rwlock &Option<T> but write locks RwLock forewer
-				let x = RwLockWriteGuard::leak(guard);
-				if let Some(value) = &mut x.0 {
-					let ptr_t: *mut T = value;
-					let ptr_seq_id: *mut SequenceNumber = &raw mut x.1;
-					(ptr_t, ptr_seq_id)
-				} else {
-					return Err(Error::NoValueSet { port: port.into() });
-				}
-			} else {
-				return Err(Error::IsLocked { port: port.into() });
-			}
-		};
+		if !value.raw().try_lock_exclusive() {
+			return Err(Error::IsLocked { port: port.into() });
+		}
+		Self::locked(port, value)
+	}
 
-		Ok(Self {
-			value,
-			ptr_t,
-			ptr_seq_id,
-			modified: false,
-		})
+	/// Creates the guard for an already write locked `value`, the lock is released on error.
+	#[allow(unsafe_code)]
+	fn locked(port: impl Into<ConstString>, value: PortValuePtr<T>) -> Result<Self> {
+		let ptr_port_value = value.data_ptr();
+		// SAFETY: the value is write locked and the guard owns the value
+		if let Some(inner) = unsafe { &mut (*ptr_port_value).value } {
+			let ptr_t: *mut T = inner;
+			Ok(Self {
+				value,
+				ptr_t,
+				ptr_port_value,
+				modified: false,
+			})
+		} else {
+			// SAFETY: the value has been write locked above
+			unsafe {
+				value.raw().unlock_exclusive();
+			}
+			Err(Error::NoValueSet { port: port.into() })
+		}
 	}
 }
 
@@ -280,4 +618,33 @@ mod tests {
 		is_normal::<&PortValue<i32>>();
 		is_normal::<PortValue<String>>();
 	}
+
+	#[test]
+	fn wakers() {
+		let mut wakers = Wakers::default();
+		wakers.register(1, Waker::noop());
+		wakers.register(1, Waker::noop());
+		wakers.register(2, Waker::noop());
+		assert_eq!(wakers.len(), 2);
+		wakers.remove(1);
+		assert_eq!(wakers.len(), 1);
+		assert_eq!(wakers.take().len(), 1);
+		assert_eq!(wakers.len(), 0);
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn timed_out_waits() {
+		use core::time::Duration;
+
+		use crate::port_changed::PortChanged;
+
+		let value = PortValuePtr::new(PortValue::<i32>::default());
+		for _ in 0..10 {
+			let result = crate::wait::wait_for("value".into(), PortChanged::new(value.clone(), 0), Duration::from_millis(1));
+			assert!(matches!(result, Err(Error::Timeout { .. })));
+		}
+		// the waker of each timed out wait has been removed
+		assert_eq!(value.read().wakers.len(), 0);
+	}
 }
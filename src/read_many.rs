@@ -0,0 +1,134 @@
+// Copyright © 2025 Stephan Kunz
+//! Consistent reading of several [`Port`]s.
+
+use core::any::Any;
+
+use crate::{
+	ConstString,
+	error::{Error, Result},
+	port::Port,
+	port_value::{PortValuePtr, PortValueReadGuard, SharedLock},
+	traits::{PortCommons, PortProvider},
+};
+
+/// A tuple of value types, which can be read together, see [`PortAccessors::read_many`](crate::PortAccessors::read_many).
+///
+/// Implemented for tuples of up to 8 types.
+pub trait ReadMany {
+	/// A tuple of port names with the same arity.
+	type Names<'a>;
+
+	/// The tuple of read guards.
+	type Guards;
+
+	/// Read locks the values of the ports named `names` and returns the guards.
+	/// # Errors
+	/// - [`Error::NotFound`], if a port is not provided.
+	/// - [`Error::LockFree`], if a port is lock-free.
+	/// - [`Error::NoValueSet`], if a port has no value of the expected type or does not yet contain a value.
+	fn read_many<P: PortProvider + ?Sized>(provider: &P, names: Self::Names<'_>) -> Result<Self::Guards>;
+}
+
+/// A tuple of cloneable value types, which can be copied together, see [`PortAccessors::snapshot`](crate::PortAccessors::snapshot).
+pub trait Snapshot: ReadMany {
+	/// The tuple of values, each together with its sequence number.
+	type Values;
+
+	/// Returns copies of the values locked by `guards`.
+	fn snapshot(guards: &Self::Guards) -> Self::Values;
+}
+
+/// Returns the name and value of type `T` of the port named `name`.
+fn value_of<T: Any + Send + Sync, P: PortProvider + ?Sized>(
+	provider: &P,
+	name: &str,
+) -> Result<(ConstString, PortValuePtr<T>)> {
+	let port: Port = provider
+		.find(name)
+		.ok_or_else(|| Error::NotFound { port: name.into() })?;
+	// port must have a value of the wanted type
+	port.as_value::<T>()
+		.map(|value| (port.name(), value))
+		.ok_or_else(|| port.no_value::<T>(name.into()))
+}
+
+/// Read locks the `values` in the order of their addresses,
+/// so that no deadlock occurs with writers locking in the same order.
+fn lock_ordered<const N: usize>(mut values: [&dyn SharedLock; N]) {
+	values.sort_by_key(|value| value.address());
+	for value in values {
+		value.lock_shared();
+	}
+}
+
+macro_rules! read_many {
+	($($tp:ident => $idx:tt),+) => {
+		impl<$($tp: Any + Send + Sync),+> ReadMany for ($($tp,)+) {
+			type Names<'a> = ($(read_many!(@name $tp),)+);
+			type Guards = ($(PortValueReadGuard<$tp>,)+);
+
+			fn read_many<P: PortProvider + ?Sized>(provider: &P, names: Self::Names<'_>) -> Result<Self::Guards> {
+				let values = ($(value_of::<$tp, P>(provider, names.$idx)?,)+);
+				lock_ordered([$(&values.$idx.1 as &dyn SharedLock),+]);
+				// all guards are created before returning an error, to release every lock
+				let guards = ($(PortValueReadGuard::locked(values.$idx.0, values.$idx.1),)+);
+				Ok(($(guards.$idx?,)+))
+			}
+		}
+
+		impl<$($tp: Any + Clone + Send + Sync),+> Snapshot for ($($tp,)+) {
+			type Values = ($(($tp, u32),)+);
+
+			fn snapshot(guards: &Self::Guards) -> Self::Values {
+				($(((*guards.$idx).clone(), guards.$idx.sequence_number()),)+)
+			}
+		}
+	};
+	(@name $tp:ident) => { &'a str };
+}
+
+read_many!(T0 => 0);
+read_many!(T0 => 0, T1 => 1);
+read_many!(T0 => 0, T1 => 1, T2 => 2);
+read_many!(T0 => 0, T1 => 1, T2 => 2, T3 => 3);
+read_many!(T0 => 0, T1 => 1, T2 => 2, T3 => 3, T4 => 4);
+read_many!(T0 => 0, T1 => 1, T2 => 2, T3 => 3, T4 => 4, T5 => 5);
+read_many!(T0 => 0, T1 => 1, T2 => 2, T3 => 3, T4 => 4, T5 => 5, T6 => 6);
+read_many!(T0 => 0, T1 => 1, T2 => 2, T3 => 3, T4 => 4, T5 => 5, T6 => 6, T7 => 7);
+
+#[cfg(test)]
+mod tests {
+	#![allow(clippy::unwrap_used)]
+	use crate::{
+		PortAccessors,
+		port_list::PortList,
+		port_value::{PortValue, PortValueWriteGuard},
+	};
+
+	use super::*;
+
+	#[test]
+	fn ordered_locking() {
+		let first = PortValuePtr::new(PortValue::<i32>::new(1));
+		let second = PortValuePtr::new(PortValue::<f64>::new(2.0));
+		lock_ordered([&second as &dyn SharedLock, &first]);
+		let guards = (
+			PortValueReadGuard::locked("first", first.clone()).unwrap(),
+			PortValueReadGuard::locked("second", second.clone()).unwrap(),
+		);
+		assert!(PortValueWriteGuard::try_new("first", first.clone()).is_err());
+		drop(guards);
+		assert!(PortValueWriteGuard::try_new("first", first).is_ok());
+		assert!(PortValueWriteGuard::try_new("second", second).is_ok());
+	}
+
+	#[test]
+	fn single_port() {
+		let list = PortList::new(alloc::vec![Port::create_out_port::<i32>("a")]);
+		assert!(list.read_many::<(i32,)>(("a",)).is_err());
+		list.set::<i32>("a", 1).unwrap();
+		let (a,) = list.read_many::<(i32,)>(("a",)).unwrap();
+		assert_eq!(*a, 1);
+		assert_eq!(a.sequence_number(), 1);
+	}
+}
@@ -0,0 +1,178 @@
+// Copyright © 2025 Stephan Kunz
+//! Implementation of a hierarchical [`ScopedPortDataBase`].
+
+use core::any::Any;
+
+use alloc::{collections::btree_map::BTreeMap, sync::Arc, vec::Vec};
+
+use crate::{
+	ConstString, RwLock,
+	error::{Error, Result},
+	port::Port,
+	port_data_base::PortDataBase,
+	traits::{InOutBound, PortAccessors, PortProvider},
+};
+
+/// A database like container for [`Port`]s with an optional parent scope.
+///
+/// A key is looked up
+/// - in the parent under the remapped key, if the key is remapped,
+/// - otherwise in the own scope and, if it is missing there, in the parent scope.
+#[derive(Default)]
+pub struct ScopedPortDataBase {
+	/// The [`Port`]s of this scope.
+	ports: RwLock<PortDataBase>,
+	/// The parent scope.
+	parent: Option<Arc<Self>>,
+	/// Remappings of own keys to keys of the parent scope.
+	remappings: BTreeMap<ConstString, ConstString>,
+}
+
+impl core::fmt::Debug for ScopedPortDataBase {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("ScopedPortDataBase")
+			.field("ports", &self.ports)
+			.field("parent", &self.parent)
+			.field("remappings", &self.remappings)
+			.finish()
+	}
+}
+
+impl PortProvider for ScopedPortDataBase {
+	fn find(&self, name: impl Into<ConstString>) -> Option<Port> {
+		let name = name.into();
+		if let Some(parent) = &self.parent {
+			if let Some(key) = self.remappings.get(&name) {
+				return parent.find(key.clone());
+			}
+			if let Some(port) = self.ports.read().find(name.clone()) {
+				return Some(port);
+			}
+			return parent.find(name);
+		}
+		self.ports.read().find(name)
+	}
+
+	/// Returns the [`Port`]s stored in this scope, without the ones of the parent scopes.
+	fn provided_ports(&self) -> impl Iterator<Item = Port> {
+		self.ports
+			.read()
+			.provided_ports()
+			.collect::<Vec<_>>()
+			.into_iter()
+	}
+}
+
+impl PortAccessors for ScopedPortDataBase {}
+
+impl ScopedPortDataBase {
+	/// Creates a [`ScopedPortDataBase`] as child scope of `parent`.
+	#[must_use]
+	pub fn with_parent(parent: Arc<Self>) -> Self {
+		Self {
+			ports: RwLock::default(),
+			parent: Some(parent),
+			remappings: BTreeMap::new(),
+		}
+	}
+
+	/// Returns the parent scope, if there is one.
+	#[must_use]
+	pub const fn parent(&self) -> Option<&Arc<Self>> {
+		self.parent.as_ref()
+	}
+
+	/// Remaps `key` of this scope to `parent_key` of the parent scope.
+	/// # Errors
+	/// - [`Error::AlreadyExists`] if `key` is already remapped or contained in this scope.
+	/// - [`Error::NotFound`] if there is no parent scope.
+	pub fn remap(&mut self, key: impl Into<ConstString>, parent_key: impl Into<ConstString>) -> Result<()> {
+		let key = key.into();
+		if self.parent.is_none() {
+			return Err(Error::NotFound { port: key });
+		}
+		if self.remappings.contains_key(&key) || self.ports.read().contains_key(&key) {
+			return Err(Error::AlreadyExists { port: key });
+		}
+		self.remappings.insert(key, parent_key.into());
+		Ok(())
+	}
+
+	/// Returns the key of the parent scope, that `key` is remapped to.
+	#[must_use]
+	pub fn remapping(&self, key: &str) -> Option<ConstString> {
+		self.remappings.get(key).cloned()
+	}
+
+	/// Returns `true` if a [`Port`] with name `key` is available in this or a parent scope, otherwise `false`.
+	#[must_use]
+	pub fn contains_key(&self, key: &str) -> bool {
+		self.find(key).is_some()
+	}
+
+	/// Returns `true` if a [`Port`] with name `key` is contained in this scope, otherwise `false`.
+	/// Remapped keys and keys of parent scopes are not considered.
+	#[must_use]
+	pub fn contains_local_key(&self, key: &str) -> bool {
+		self.ports.read().contains_key(key)
+	}
+
+	/// Creates a [`Port`] with value of type `T` under `key`.
+	/// A remapped `key` is created in the parent scope, otherwise in this scope.
+	/// # Errors
+	/// - [`Error::AlreadyExists`] if `key` already exists.
+	pub fn create<T: Any + Send + Sync>(&self, key: impl Into<ConstString>, value: impl Into<T>) -> Result<()> {
+		let key = key.into();
+		if let Some(parent) = &self.parent
+			&& let Some(parent_key) = self.remappings.get(&key)
+		{
+			return parent.create::<T>(parent_key.clone(), value);
+		}
+		self.ports.write().create::<T>(key, value)
+	}
+
+	/// Returns the value of type `T` stored in the [`Port`] under `key` and deletes it from storage.
+	/// A remapped `key` is deleted in the parent scope, other keys of parent scopes are not touched.
+	/// # Errors
+	/// - [`Error::NotFound`]  if `key` is not contained.
+	/// - [`Error::WrongType`] if the entry has not the expected type `T`.
+	pub fn delete<T: Any + Send + Sync>(&self, key: &str) -> Result<T> {
+		if let Some(parent) = &self.parent
+			&& let Some(parent_key) = self.remappings.get(key)
+		{
+			return parent.delete::<T>(parent_key);
+		}
+		self.ports.write().delete::<T>(key)
+	}
+
+	/// Returns the [`Port`] available under `key` in this or a parent scope.
+	/// # Errors
+	/// - [`Error::NotFound`] if `key` is not available.
+	pub fn port(&self, key: &str) -> Result<Port> {
+		self.find(key)
+			.ok_or_else(|| Error::NotFound { port: key.into() })
+	}
+
+	/// Updates a value of type `T` available under `key` in this or a parent scope and returns the old value.
+	/// # Errors
+	/// - [`Error::NotFound`]  if `key` is not available.
+	/// - [`Error::WrongType`] if the [`Port`] has not the expected type `T`.
+	pub fn update<T: Any + Send + Sync>(&self, key: &str, value: impl Into<T>) -> Result<Option<T>> {
+		let port = self.port(key)?;
+		port.as_in_out_port::<T>()
+			.map_or_else(|| Err(port.wrong_type::<T>()), |port| Ok(port.replace(value.into())))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<ScopedPortDataBase>();
+	}
+}
@@ -6,6 +6,10 @@
 pub(crate) struct SequenceNumber(u32);
 
 impl SequenceNumber {
+	pub const fn new(value: u32) -> Self {
+		Self(value)
+	}
+
 	pub fn increment(&mut self) {
 		if self.0 < u32::MAX {
 			self.0 += 1;
@@ -17,6 +21,14 @@ impl SequenceNumber {
 	pub const fn value(&self) -> u32 {
 		self.0
 	}
+
+	/// Returns `true` if `value` has been assigned after `other`, taking the wrap around into account.
+	/// `0` is the state before the first change, every other value is after it.
+	pub const fn is_after(value: u32, other: u32) -> bool {
+		#[allow(clippy::cast_possible_wrap)]
+		let distance = value.wrapping_sub(other) as i32;
+		value != 0 && (other == 0 || distance > 0)
+	}
 }
 
 #[cfg(test)]
@@ -44,4 +56,17 @@ mod tests {
 		sq.increment();
 		assert_eq!(sq.0, 1);
 	}
+
+	#[test]
+	fn ordering() {
+		assert!(SequenceNumber::is_after(2, 1));
+		assert!(!SequenceNumber::is_after(1, 2));
+		assert!(!SequenceNumber::is_after(1, 1));
+		assert!(SequenceNumber::is_after(1, 0));
+		assert!(SequenceNumber::is_after(u32::MAX, 0));
+		assert!(!SequenceNumber::is_after(0, 1));
+		assert!(SequenceNumber::is_after(1, u32::MAX));
+		assert!(SequenceNumber::is_after(2, u32::MAX - 1));
+		assert!(!SequenceNumber::is_after(u32::MAX, 1));
+	}
 }
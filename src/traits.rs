@@ -1,15 +1,18 @@
 // Copyright © 2025 Stephan Kunz
 //! Traits for working with ports and lists of ports.
 
-use core::any::Any;
+use core::any::{Any, TypeId};
+
+use alloc::{sync::Arc, vec::Vec};
 
 use crate::{
 	ConstString,
 	error::{Error, Result},
-	in_out_port::InOutBoundPort,
-	in_port::InBoundPort,
-	port::Port,
+	port::{Port, PortDirection},
+	port_changed::PortChanged,
+	port_queue,
 	port_value::{PortValueReadGuard, PortValueWriteGuard},
+	read_many::{ReadMany, Snapshot},
 };
 
 /// The `AnyPort` trait allows to send ports between threads.
@@ -22,11 +25,27 @@ pub(crate) trait AnyPort: Any + Send + Sync + core::fmt::Debug + PortCommons {
 	/// Convert to mut Any
 	#[must_use]
 	fn as_mut_any(&mut self) -> &mut dyn Any;
+
+	/// Returns the name of the ports value type.
+	#[must_use]
+	fn value_type_name(&self) -> &'static str;
+
+	/// Returns the [`TypeId`] of the ports value type.
+	#[must_use]
+	fn value_type_id(&self) -> TypeId;
+
+	/// Returns the direction of the port.
+	#[must_use]
+	fn direction(&self) -> PortDirection;
+
+	/// Returns the address of the ports value.
+	#[must_use]
+	fn value_address(&self) -> usize;
 }
 
 /// Blanket implementation for any type that has a `static` lifetime and implements
-/// [`core::fmt::Debug`], [`PortCommons`], [`Send`] and [`Sync`].
-impl<T: 'static + core::fmt::Debug + PortCommons + Send + Sync> AnyPort for T {
+/// [`core::fmt::Debug`], [`PortCommons`], [`PortInfo`], [`Send`] and [`Sync`].
+impl<T: 'static + core::fmt::Debug + PortCommons + PortInfo + Send + Sync> AnyPort for T {
 	fn as_any(&self) -> &dyn Any {
 		self
 	}
@@ -34,6 +53,34 @@ impl<T: 'static + core::fmt::Debug + PortCommons + Send + Sync> AnyPort for T {
 	fn as_mut_any(&mut self) -> &mut dyn Any {
 		self
 	}
+
+	fn value_type_name(&self) -> &'static str {
+		core::any::type_name::<T::Value>()
+	}
+
+	fn value_type_id(&self) -> TypeId {
+		TypeId::of::<T::Value>()
+	}
+
+	fn direction(&self) -> PortDirection {
+		T::DIRECTION
+	}
+
+	fn value_address(&self) -> usize {
+		PortInfo::value_address(self)
+	}
+}
+
+/// Static type information of a port type.
+pub(crate) trait PortInfo {
+	/// The type of the ports value.
+	type Value: Any;
+
+	/// The direction of the port.
+	const DIRECTION: PortDirection;
+
+	/// Returns the address of the ports value, ports sharing their value have the same address.
+	fn value_address(&self) -> usize;
 }
 
 /// Common features for all types of ports.
@@ -46,6 +93,26 @@ pub trait PortCommons {
 	/// Returns the change sequence number, which wraps around to `1` after reaching u32::MAX.
 	/// A sequence id of `0` means that the value has never been set or changed.
 	fn sequence_number(&self) -> u32;
+
+	/// Returns `true` if the port is bound to a source port, otherwise `false`.
+	#[must_use]
+	fn is_bound(&self) -> bool;
+
+	/// Returns the name of the source port, if the port is bound.
+	#[must_use]
+	fn bound_source_name(&self) -> Option<ConstString>;
+
+	/// Returns `true` if the port must be bound before use, otherwise `false`.
+	#[must_use]
+	fn is_required(&self) -> bool {
+		false
+	}
+
+	/// Returns `true` if the port has a default value, which is used while it is not bound, otherwise `false`.
+	#[must_use]
+	fn has_default(&self) -> bool {
+		false
+	}
 }
 
 /// Trait for bound incoming port types.
@@ -68,6 +135,20 @@ pub trait InBound<T>: PortCommons {
 	/// - [`Error::NotFound`], if port is not in port list.
 	/// - [`Error::WrongType`], if port is not the expected port type & type of T.
 	fn try_read(&self) -> Result<PortValueReadGuard<T>>;
+
+	/// Registers a `callback`, which is called after each change of the ports value
+	/// with the name of this port and the new sequence number.
+	/// The callback is called after the lock on the value has been released.
+	/// It follows the port when the port is (re)bound.
+	fn on_change(&self, callback: impl Fn(&str, u32) + Send + Sync + 'static);
+
+	/// Returns a future, which resolves to the new sequence number after the next change of the ports value.
+	fn changed(&self) -> PortChanged<T> {
+		self.wait_for_change(self.sequence_number())
+	}
+
+	/// Returns a future, which resolves to the current sequence number as soon as it differs from `since`.
+	fn wait_for_change(&self, since: u32) -> PortChanged<T>;
 }
 
 /// Trait for bound in/out port types.
@@ -103,8 +184,14 @@ pub trait OutBound<T>: PortCommons {
 /// Something that provides ports.
 pub trait PortProvider {
 	/// Lookup a [`Port`].
+	/// The returned [`Port`] shares its state with the provided one.
 	#[must_use]
-	fn find(&self, name: impl Into<ConstString>) -> Option<&Port>;
+	fn find(&self, name: impl Into<ConstString>) -> Option<Port>;
+
+	/// Returns an iterator over all provided [`Port`]s.
+	/// The returned [`Port`]s share their state with the provided ones.
+	/// E.g. [`PortGraph`](crate::PortGraph) and [`PortExport`](crate::PortExport) only see the ports returned here.
+	fn provided_ports(&self) -> impl Iterator<Item = Port>;
 }
 
 /// Accessors to ports.
@@ -113,7 +200,7 @@ pub trait PortAccessors: PortProvider {
 	/// Port `out` is where the value is created, `in` where it is consumed.
 	/// # Errors
 	/// - [`Error::NotFound`], if one of the ports is not in port list.
-	/// - [`Error::PortAlreadyBound`], if in port is already bound.
+	/// - [`Error::AlreadyBound`], if in port is already bound.
 	/// - [`Error::WrongType`], if one of the ports is not the needed port type & type of T.
 	fn bind_to<T: Any + Send + Sync>(
 		&self,
@@ -121,31 +208,92 @@ pub trait PortAccessors: PortProvider {
 		out_list: &impl PortAccessors,
 		out_port: impl Into<ConstString>,
 	) -> Result<()> {
-		// src is where the value is created, dest where it is consumed
-		let src_port = out_port.into();
-		if let Some(out_port) = out_list.find(src_port.clone()) {
-			// src must provide an output value of the wanted type
-			if let Some(out_value) = out_port.as_out_value::<T>() {
-				let dest_port = in_port.into();
-				if let Some(in_port) = self.find(dest_port.clone()) {
-					// dest must want input value of the wanted type
-					if let Some(input_port) = in_port.port().downcast_ref::<InBoundPort<T>>() {
-						input_port.set_value(out_value);
-						Ok(())
-					} else if let Some(input_output_port) = in_port.port().downcast_ref::<InOutBoundPort<T>>() {
-						input_output_port.set_value(out_value);
-						Ok(())
-					} else {
-						Err(Error::WrongType { port: dest_port })
-					}
-				} else {
-					Err(Error::NotFound { port: dest_port })
-				}
-			} else {
-				Err(Error::WrongType { port: src_port })
-			}
+		bind::<T>(self, in_port, out_list, out_port, false)
+	}
+
+	/// Binds the in port with a value of type `D` to the out port with a value of type `S`,
+	/// converting the value with `convert`.
+	/// The value is converted, when it is read via the in port, keeping the sequence number of the out port.
+	/// Port `out` is where the value is created, `in` where it is consumed.
+	/// # Errors
+	/// - [`Error::NotFound`], if one of the ports is not in port list.
+	/// - [`Error::AlreadyBound`], if in port is already bound.
+	/// - [`Error::WrongType`], if in port is not an [`InBoundPort`](crate::InBoundPort) of type D
+	///   or out port is not the needed port type & type of S.
+	fn bind_to_with<S: Any + Send + Sync, D: Any + Send + Sync>(
+		&self,
+		in_port: impl Into<ConstString>,
+		out_list: &impl PortAccessors,
+		out_port: impl Into<ConstString>,
+		convert: impl Fn(&S) -> D + Send + Sync + 'static,
+	) -> Result<()> {
+		with_ports(self, in_port, out_list, out_port, |in_port, out_port| {
+			in_port.bind_with::<S, D>(out_port, Arc::new(convert), false)
+		})
+	}
+
+	/// Binds the in port with a value of type `D` to the out port with a value of type `S`,
+	/// converting the value with the registered or builtin conversion, see [`Port::register_conversion`].
+	/// # Errors
+	/// - [`Error::NotFound`], if one of the ports is not in port list.
+	/// - [`Error::AlreadyBound`], if in port is already bound.
+	/// - [`Error::NoConversion`], if there is no conversion from S into D.
+	/// - [`Error::WrongType`], if in port is not an [`InBoundPort`](crate::InBoundPort) of type D
+	///   or out port is not the needed port type & type of S.
+	fn bind_to_converted<S: Any + Send + Sync, D: Any + Send + Sync>(
+		&self,
+		in_port: impl Into<ConstString>,
+		out_list: &impl PortAccessors,
+		out_port: impl Into<ConstString>,
+	) -> Result<()> {
+		with_ports(self, in_port, out_list, out_port, |in_port, out_port| {
+			in_port.bind_converted::<S, D>(out_port, false)
+		})
+	}
+
+	/// Binds the in port to the out port, replacing an existing binding of the in port.
+	/// Port `out` is where the value is created, `in` where it is consumed.
+	/// # Errors
+	/// - [`Error::NotFound`], if one of the ports is not in port list.
+	/// - [`Error::WrongType`], if one of the ports is not the needed port type & type of T.
+	fn rebind_to<T: Any + Send + Sync>(
+		&self,
+		in_port: impl Into<ConstString>,
+		out_list: &impl PortAccessors,
+		out_port: impl Into<ConstString>,
+	) -> Result<()> {
+		bind::<T>(self, in_port, out_list, out_port, true)
+	}
+
+	/// Removes the binding of the port, leaving it with its default value if it has one, otherwise with an empty value.
+	/// # Errors
+	/// - [`Error::NotBound`], if port is not bound.
+	/// - [`Error::NotFound`], if port is not in port list.
+	/// - [`Error::WrongType`], if port is not the expected port type & type of T.
+	fn unbind<T: Any + Send + Sync>(&self, port: impl Into<ConstString>) -> Result<()> {
+		let port = port.into();
+		if let Some(port_ref) = self.find(port.clone()) {
+			port_ref.unbind::<T>()
+		} else {
+			Err(Error::NotFound { port })
+		}
+	}
+
+	/// Registers a `callback` for changes of the port's value.
+	/// See [`InBound::on_change`].
+	/// # Errors
+	/// - [`Error::NotFound`], if port is not in port list.
+	/// - [`Error::WrongType`], if port is not the expected port type & type of T.
+	fn on_change<T: Any + Send + Sync>(
+		&self,
+		port: impl Into<ConstString>,
+		callback: impl Fn(&str, u32) + Send + Sync + 'static,
+	) -> Result<()> {
+		let port = port.into();
+		if let Some(port_ref) = self.find(port.clone()) {
+			port_ref.on_change::<T>(callback)
 		} else {
-			Err(Error::NotFound { port: src_port })
+			Err(Error::NotFound { port })
 		}
 	}
 
@@ -163,6 +311,9 @@ pub trait PortAccessors: PortProvider {
 				} else {
 					Err(Error::ValueNotInitialized { port })
 				}
+			} else if let Some(cell) = port_ref.as_lock_free_value::<T>() {
+				cell.load()
+					.ok_or(Error::ValueNotInitialized { port })
 			} else {
 				Err(Error::NoValueSet { port })
 			}
@@ -171,8 +322,33 @@ pub trait PortAccessors: PortProvider {
 		}
 	}
 
+	/// Returns immutable guards to the values of several ports, which belong together.
+	/// The value types are given as tuple `T`, the port names as tuple of the same arity,
+	/// like `read_many::<(f64, f64)>(("x", "y"))`.
+	/// The read locks are acquired in a stable order, so that no deadlock occurs with
+	/// [`PortDataBase::transaction`](crate::PortDataBase::transaction) or another `read_many`.
+	/// # Errors
+	/// - [`Error::LockFree`], if a port is lock-free.
+	/// - [`Error::NotFound`], if a port is not in port list.
+	/// - [`Error::NoValueSet`], if a port is not the expected type or does not yet contain a value.
+	fn read_many<T: ReadMany>(&self, ports: T::Names<'_>) -> Result<T::Guards> {
+		T::read_many(self, ports)
+	}
+
+	/// Returns copies of the values of several ports, each together with its sequence number.
+	/// The values are read consistently like with [`PortAccessors::read_many`].
+	/// # Errors
+	/// - [`Error::LockFree`], if a port is lock-free.
+	/// - [`Error::NotFound`], if a port is not in port list.
+	/// - [`Error::NoValueSet`], if a port is not the expected type or does not yet contain a value.
+	fn snapshot<T: Snapshot>(&self, ports: T::Names<'_>) -> Result<T::Values> {
+		let guards = T::read_many(self, ports)?;
+		Ok(T::snapshot(&guards))
+	}
+
 	/// Returns an immutable guard to the T.
 	/// # Errors
+	/// - [`Error::LockFree`], if port is a lock-free port.
 	/// - [`Error::NotFound`], if port is not in port list.
 	/// - [`Error::WrongType`], if port is not the expected port type & type of T.
 	fn read<T: Any + Send + Sync>(&self, port: impl Into<ConstString>) -> Result<PortValueReadGuard<T>> {
@@ -182,7 +358,7 @@ pub trait PortAccessors: PortProvider {
 			if let Some(value_ref) = port_ref.as_value::<T>() {
 				PortValueReadGuard::new(port_ref.name(), value_ref.clone())
 			} else {
-				Err(Error::NoValueSet { port })
+				Err(port_ref.no_value::<T>(port))
 			}
 		} else {
 			Err(Error::NotFound { port })
@@ -192,6 +368,7 @@ pub trait PortAccessors: PortProvider {
 	/// Returns an immutable guard to the T.
 	/// # Errors
 	/// - [`Error::IsLocked`], if port is locked.
+	/// - [`Error::LockFree`], if port is a lock-free port.
 	/// - [`Error::NotFound`], if port is not in port list.
 	/// - [`Error::WrongType`], if port is not the expected port type & type of T.
 	fn try_read<T: Any + Send + Sync>(&self, port: impl Into<ConstString>) -> Result<PortValueReadGuard<T>> {
@@ -201,7 +378,42 @@ pub trait PortAccessors: PortProvider {
 			if let Some(value_ref) = port_ref.as_value::<T>() {
 				PortValueReadGuard::try_new(port_ref.name(), value_ref.clone())
 			} else {
-				Err(Error::NoValueSet { port })
+				Err(port_ref.no_value::<T>(port))
+			}
+		} else {
+			Err(Error::NotFound { port })
+		}
+	}
+
+	/// Removes and returns the oldest value of a queue port.
+	/// # Errors
+	/// - [`Error::NotFound`], if port is not in port list.
+	/// - [`Error::WrongType`], if port is not an in-bound queue port of type T.
+	fn pop<T: Any + Send + Sync>(&self, port: impl Into<ConstString>) -> Result<Option<T>> {
+		let port = port.into();
+		if let Some(port_ref) = self.find(port.clone()) {
+			if let Some(queue) = port_ref.as_in_queue::<T>() {
+				Ok(port_queue::pop(&queue))
+			} else {
+				Err(port_ref.wrong_type::<T>())
+			}
+		} else {
+			Err(Error::NotFound { port })
+		}
+	}
+
+	/// Pushes the `value` into the queue of a queue port.
+	/// # Errors
+	/// - [`Error::NotFound`], if port is not in port list.
+	/// - [`Error::QueueFull`], if the queue is full and its policy is [`OverflowPolicy::Error`](crate::OverflowPolicy::Error).
+	/// - [`Error::WrongType`], if port is not an out-bound queue port of type T.
+	fn push<T: Any + Send + Sync>(&self, port: impl Into<ConstString>, value: impl Into<T>) -> Result<()> {
+		let port = port.into();
+		if let Some(port_ref) = self.find(port.clone()) {
+			if let Some(queue) = port_ref.as_out_queue::<T>() {
+				port_queue::push(port, &queue, value.into())
+			} else {
+				Err(port_ref.wrong_type::<T>())
 			}
 		} else {
 			Err(Error::NotFound { port })
@@ -210,15 +422,16 @@ pub trait PortAccessors: PortProvider {
 
 	/// Replaces the port's value with the `value` and returns the old value.
 	/// # Errors
+	/// - [`Error::LockFree`], if port is a lock-free port.
 	/// - [`Error::NotFound`], if port is not in port list.
 	/// - [`Error::WrongType`], if port is not the expected port type & type of T.
 	fn replace<T: Any + Send + Sync>(&self, port: &str, value: impl Into<T>) -> Result<Option<T>> {
 		if let Some(port_ref) = self.find(port) {
 			// port must have a value of the wanted type
 			if let Some(value_ref) = port_ref.as_value::<T>() {
-				Ok(value_ref.write().replace(value))
+				Ok(value_ref.replace(value))
 			} else {
-				Err(Error::NoValueSet { port: port.into() })
+				Err(port_ref.no_value::<T>(port.into()))
 			}
 		} else {
 			Err(Error::NotFound { port: port.into() })
@@ -237,8 +450,36 @@ pub trait PortAccessors: PortProvider {
 		}
 	}
 
+	/// Blocks the current thread until the sequence number of the port differs from `since`
+	/// and returns the new sequence number.
+	/// A `timeout` of [`Duration::MAX`](core::time::Duration::MAX) waits without a deadline.
+	/// # Errors
+	/// - [`Error::NotFound`], if port is not in port list.
+	/// - [`Error::Timeout`], if the sequence number did not change within `timeout`.
+	/// - [`Error::WrongType`], if port is not the expected port type & type of T.
+	#[cfg(feature = "std")]
+	fn wait_for<T: Any + Send + Sync>(
+		&self,
+		port: impl Into<ConstString>,
+		since: u32,
+		timeout: core::time::Duration,
+	) -> Result<u32> {
+		let port = port.into();
+		if let Some(port_ref) = self.find(port.clone()) {
+			// port must have a value of the wanted type
+			if let Some(changed) = port_ref.changed::<T>(since) {
+				crate::wait::wait_for(port, changed, timeout)
+			} else {
+				Err(port_ref.wrong_type::<T>())
+			}
+		} else {
+			Err(Error::NotFound { port })
+		}
+	}
+
 	/// Sets the port to the value.
 	/// # Errors
+	/// - [`Error::LockFree`], if port is a lock-free in-bound port.
 	/// - [`Error::NotFound`], if port is not in port list.
 	/// - [`Error::WrongType`], if port is not the expected port type & type of T.
 	fn set<T: Any + Send + Sync>(&self, port: impl Into<ConstString>, value: impl Into<T>) -> Result<()> {
@@ -246,18 +487,37 @@ pub trait PortAccessors: PortProvider {
 		if let Some(port_ref) = self.find(port.clone()) {
 			// port must have a value of the wanted type
 			if let Some(value_ref) = port_ref.as_value::<T>() {
-				value_ref.write().set(value);
+				value_ref.set(value);
+				Ok(())
+			} else if let Some(cell) = port_ref.as_lock_free_cell::<T>() {
+				cell.set(Some(value.into()));
 				Ok(())
 			} else {
-				Err(Error::NoValueSet { port })
+				Err(port_ref.no_value::<T>(port))
 			}
 		} else {
 			Err(Error::NotFound { port })
 		}
 	}
 
+	/// Sets the port to the `value` parsed from a string, see [`Port::set_from_str`].
+	/// # Errors
+	/// - [`Error::LockFree`], if the port is a lock-free in port.
+	/// - [`Error::NotFound`], if port is not in port list.
+	/// - [`Error::NotSettable`], if the port has no single value or a computed or converted value.
+	/// - [`Error::ParseFailed`], if the value cannot be parsed into the ports type.
+	fn set_str(&self, port: impl Into<ConstString>, value: &str) -> Result<()> {
+		let port = port.into();
+		if let Some(port_ref) = self.find(port.clone()) {
+			port_ref.set_from_str(value)
+		} else {
+			Err(Error::NotFound { port })
+		}
+	}
+
 	/// Returns the value of that port.
 	/// # Errors
+	/// - [`Error::LockFree`], if port is a lock-free port.
 	/// - [`Error::NotFound`], if port is not in port list.
 	/// - [`Error::WrongType`], if port is not the expected port type & type of T.
 	fn take<T: Any + Clone + Send + Sync>(&self, port: impl Into<ConstString>) -> Result<Option<T>> {
@@ -265,9 +525,9 @@ pub trait PortAccessors: PortProvider {
 		if let Some(port_ref) = self.find(port.clone()) {
 			// port must have a value of the wanted type
 			if let Some(value_ref) = port_ref.as_value::<T>() {
-				Ok(value_ref.write().take())
+				Ok(value_ref.take())
 			} else {
-				Err(Error::NoValueSet { port })
+				Err(port_ref.no_value::<T>(port))
 			}
 		} else {
 			Err(Error::NotFound { port })
@@ -276,6 +536,7 @@ pub trait PortAccessors: PortProvider {
 
 	/// Returns a mutable guard to the T.
 	/// # Errors
+	/// - [`Error::LockFree`], if port is a lock-free port.
 	/// - [`Error::NotFound`], if port is not in port list.
 	/// - [`Error::WrongType`], if port is not the expected port type & type of T.
 	fn write<T: Any + Send + Sync>(&self, port: impl Into<ConstString>) -> Result<PortValueWriteGuard<T>> {
@@ -285,7 +546,7 @@ pub trait PortAccessors: PortProvider {
 			if let Some(value_ref) = port_ref.as_value::<T>() {
 				PortValueWriteGuard::new(port_ref.name(), value_ref.clone())
 			} else {
-				Err(Error::NoValueSet { port })
+				Err(port_ref.no_value::<T>(port))
 			}
 		} else {
 			Err(Error::NotFound { port })
@@ -295,6 +556,7 @@ pub trait PortAccessors: PortProvider {
 	/// Returns a mutable guard to the T.
 	/// # Errors
 	/// - [`Error::IsLocked`], if port is locked.
+	/// - [`Error::LockFree`], if port is a lock-free port.
 	/// - [`Error::NotFound`], if port is not in port list.
 	/// - [`Error::WrongType`], if port is not the expected port type & type of T.
 	fn try_write<T: Any + Send + Sync>(&self, port: impl Into<ConstString>) -> Result<PortValueWriteGuard<T>> {
@@ -304,7 +566,7 @@ pub trait PortAccessors: PortProvider {
 			if let Some(value_ref) = port_ref.as_value::<T>() {
 				PortValueWriteGuard::try_new(port_ref.name(), value_ref.clone())
 			} else {
-				Err(Error::NoValueSet { port })
+				Err(port_ref.no_value::<T>(port))
 			}
 		} else {
 			Err(Error::NotFound { port })
@@ -312,9 +574,89 @@ pub trait PortAccessors: PortProvider {
 	}
 }
 
+/// Filtered iteration over a collection of ports.
+pub trait PortIterators {
+	/// Returns an iterator over all [`Port`]s.
+	fn ports(&self) -> impl Iterator<Item = &Port>;
+
+	/// Returns an iterator over the [`Port`]s consuming values, including in/out ports.
+	fn inputs(&self) -> impl Iterator<Item = &Port> {
+		self.ports()
+			.filter(|port| port.direction().is_input())
+	}
+
+	/// Returns an iterator over the [`Port`]s producing values, including in/out ports.
+	fn outputs(&self) -> impl Iterator<Item = &Port> {
+		self.ports()
+			.filter(|port| port.direction().is_output())
+	}
+
+	/// Returns an iterator over the [`Port`]s with a value of type `T`.
+	fn of_type<T: Any>(&self) -> impl Iterator<Item = &Port> {
+		self.ports()
+			.filter(|port| port.value_type_id() == TypeId::of::<T>())
+	}
+
+	/// Returns an iterator over the [`Port`]s consuming values, which are not bound to a source.
+	fn unbound_inputs(&self) -> impl Iterator<Item = &Port> {
+		self.inputs().filter(|port| !port.is_bound())
+	}
+
+	/// Checks that all required [`Port`]s are bound, which should be done before execution begins.
+	/// # Errors
+	/// - [`Error::RequiredNotBound`] with the names of the required ports, which are not bound.
+	fn validate(&self) -> Result<()> {
+		let ports: Vec<ConstString> = self
+			.ports()
+			.filter(|port| port.is_required() && !port.is_bound())
+			.map(PortCommons::name)
+			.collect();
+		if ports.is_empty() {
+			Ok(())
+		} else {
+			Err(Error::RequiredNotBound { ports })
+		}
+	}
+}
+
+/// Helper function for binding and rebinding ports.
+fn bind<T: Any + Send + Sync>(
+	in_list: &(impl PortProvider + ?Sized),
+	in_port: impl Into<ConstString>,
+	out_list: &impl PortProvider,
+	out_port: impl Into<ConstString>,
+	rebind: bool,
+) -> Result<()> {
+	with_ports(in_list, in_port, out_list, out_port, |in_port, out_port| {
+		in_port.bind::<T>(out_port, rebind)
+	})
+}
+
+/// Helper function applying `f` to the in port and the out port of a binding.
+fn with_ports(
+	in_list: &(impl PortProvider + ?Sized),
+	in_port: impl Into<ConstString>,
+	out_list: &impl PortProvider,
+	out_port: impl Into<ConstString>,
+	f: impl FnOnce(&Port, &Port) -> Result<()>,
+) -> Result<()> {
+	// src is where the value is created, dest where it is consumed
+	let src_port = out_port.into();
+	if let Some(out_port) = out_list.find(src_port.clone()) {
+		let dest_port = in_port.into();
+		if let Some(in_port) = in_list.find(dest_port.clone()) {
+			f(&in_port, &out_port)
+		} else {
+			Err(Error::NotFound { port: dest_port })
+		}
+	} else {
+		Err(Error::NotFound { port: src_port })
+	}
+}
+
 #[cfg(test)]
 mod tests {
-	use crate::{in_port::InBoundPort, out_port::OutBoundPort};
+	use crate::{in_out_port::InOutBoundPort, in_port::InBoundPort, out_port::OutBoundPort};
 
 	use super::*;
 
@@ -345,6 +687,26 @@ mod tests {
 		*src.write().unwrap() = 24;
 	}
 
+	struct Provider(InBoundPort<i32>);
+
+	impl PortProvider for Provider {
+		fn find(&self, name: impl Into<ConstString>) -> Option<Port> {
+			(*name.into() == *self.0.name()).then(|| Port::from(&self.0))
+		}
+
+		fn provided_ports(&self) -> impl Iterator<Item = Port> {
+			core::iter::once(Port::from(&self.0))
+		}
+	}
+
+	#[test]
+	fn custom_provider() {
+		let provider = Provider(InBoundPort::new("port"));
+		assert!(provider.find("port").is_some());
+		assert!(provider.find("other").is_none());
+		assert_eq!(provider.provided_ports().count(), 1);
+	}
+
 	#[test]
 	fn impl_compatibility() {
 		let in_port = return_impl_in_port();
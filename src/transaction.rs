@@ -0,0 +1,252 @@
+// Copyright © 2025 Stephan Kunz
+//! Implementation of a [`Transaction`] on a [`PortDataBase`].
+
+use core::any::Any;
+
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{
+	error::Result,
+	lock::RawRwLock,
+	port_data_base::PortDataBase,
+	port_value::{Notification, PortValuePtr},
+};
+
+/// A type erased change of a value staged in a [`Transaction`].
+trait StagedChange {
+	/// Returns the address of the changed value.
+	fn address(&self) -> usize;
+
+	/// Acquires the write lock of the changed value.
+	fn lock(&self);
+
+	/// Applies the change to the write locked value and returns the pending notification.
+	fn apply(&mut self) -> Notification;
+
+	/// Releases the write lock of the changed value.
+	fn unlock(&self);
+}
+
+/// A new value for a [`PortValuePtr`].
+struct Change<T> {
+	/// The changed value.
+	target: PortValuePtr<T>,
+	/// The new value, which is taken on applying.
+	value: Option<T>,
+}
+
+impl<T> StagedChange for Change<T> {
+	fn address(&self) -> usize {
+		self.target.address()
+	}
+
+	fn lock(&self) {
+		self.target.raw().lock_exclusive();
+	}
+
+	#[allow(unsafe_code)]
+	fn apply(&mut self) -> Notification {
+		// SAFETY: the value is write locked by the transaction
+		let target = unsafe { &mut *self.target.data_ptr() };
+		if let Some(value) = self.value.take() {
+			target.set(value);
+		}
+		target.notification()
+	}
+
+	#[allow(unsafe_code)]
+	fn unlock(&self) {
+		// SAFETY: the value has been write locked by `lock`
+		unsafe {
+			self.target.raw().unlock_exclusive();
+		}
+	}
+}
+
+/// The write locks of the staged changes, which are released on drop, even if applying a change panics.
+struct Locked<'c> {
+	/// The changes in locking order.
+	changes: &'c mut [Box<dyn StagedChange>],
+	/// The number of changes, whose write lock is acquired.
+	count: usize,
+}
+
+impl<'c> Locked<'c> {
+	/// Acquires the write locks of the `changes` in the given order.
+	fn acquire(changes: &'c mut [Box<dyn StagedChange>]) -> Self {
+		let mut locked = Self { changes, count: 0 };
+		while let Some(change) = locked.changes.get(locked.count) {
+			change.lock();
+			locked.count += 1;
+		}
+		locked
+	}
+
+	/// Applies the changes and returns the pending notifications.
+	fn apply(&mut self) -> Vec<Notification> {
+		self.changes
+			.iter_mut()
+			.map(|change| change.apply())
+			.collect()
+	}
+}
+
+impl Drop for Locked<'_> {
+	fn drop(&mut self) {
+		for change in &self.changes[..self.count] {
+			change.unlock();
+		}
+	}
+}
+
+/// Transaction
+///
+/// Collects changes of several values of a [`PortDataBase`], which are applied as a unit,
+/// see [`PortDataBase::transaction`].
+pub struct Transaction<'a> {
+	/// The [`PortDataBase`] containing the changed values.
+	ports: &'a PortDataBase,
+	/// The staged changes, at most one per value.
+	changes: Vec<Box<dyn StagedChange>>,
+}
+
+impl core::fmt::Debug for Transaction<'_> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("Transaction")
+			.field("changes", &self.changes.len())
+			.finish_non_exhaustive()
+	}
+}
+
+impl<'a> Transaction<'a> {
+	pub(crate) const fn new(ports: &'a PortDataBase) -> Self {
+		Self {
+			ports,
+			changes: Vec::new(),
+		}
+	}
+
+	/// Stages `value` as new value of type `T` stored under `key`,
+	/// replacing a value staged before for the same `key`.
+	/// The values of a [`PortDataBase`] are never lock-free, so each of them can take part in a transaction.
+	/// # Errors
+	/// - [`Error::NotFound`](crate::Error::NotFound) if `key` is not contained.
+	/// - [`Error::WrongType`](crate::Error::WrongType) if the [`Port`](crate::Port) has not the expected type `T`.
+	pub fn set<T: Any + Send + Sync>(&mut self, key: &str, value: impl Into<T>) -> Result<()> {
+		let port = self.ports.port(key)?;
+		let target = port
+			.as_in_out_port::<T>()
+			.ok_or_else(|| port.wrong_type::<T>())?
+			.value();
+		let address = target.address();
+		self.changes
+			.retain(|change| change.address() != address);
+		self.changes.push(Box::new(Change {
+			target,
+			value: Some(value.into()),
+		}));
+		Ok(())
+	}
+
+	/// Applies the staged changes.
+	/// All write locks are acquired in the order of the value addresses, to avoid deadlocks between transactions,
+	/// and released after all changes are applied, or when applying a change panics.
+	/// The observers are notified after releasing the locks.
+	pub(crate) fn commit(mut self) {
+		self.changes
+			.sort_by_key(|change| change.address());
+		let notifications = Locked::acquire(&mut self.changes).apply();
+		for notification in notifications {
+			notification.notify();
+		}
+	}
+}
+
+impl PortDataBase {
+	/// Changes several values as a unit.
+	/// The changes staged by `f` via [`Transaction::set`] are applied, if `f` succeeds, and are discarded otherwise.
+	/// Readers see either none or all of the changes, each changed value gets a new sequence number.
+	/// # Errors
+	/// - the [`Error`](crate::Error) returned by `f`.
+	pub fn transaction<R>(&self, f: impl FnOnce(&mut Transaction<'_>) -> Result<R>) -> Result<R> {
+		let mut transaction = Transaction::new(self);
+		let result = f(&mut transaction)?;
+		transaction.commit();
+		Ok(result)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#![allow(clippy::unwrap_used)]
+	use crate::{error::Error, traits::PortCommons};
+
+	use super::*;
+
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	// check, that the auto traits are available.
+	#[test]
+	const fn normal_types() {
+		is_normal::<&PortDataBase>();
+	}
+
+	#[test]
+	fn staging() {
+		let mut db = PortDataBase::default();
+		db.create::<i32>("x", 1).unwrap();
+		db.create::<f64>("y", 2.0).unwrap();
+		let mut transaction = Transaction::new(&db);
+		transaction.set::<i32>("x", 2).unwrap();
+		transaction.set::<f64>("y", 3.0).unwrap();
+		transaction.set::<i32>("x", 3).unwrap();
+		assert_eq!(transaction.changes.len(), 2);
+		assert!(matches!(transaction.set::<i32>("y", 1), Err(Error::WrongType { .. })));
+		assert!(matches!(transaction.set::<i32>("z", 1), Err(Error::NotFound { .. })));
+		assert_eq!(db.port("x").unwrap().sequence_number(), 1);
+		transaction.commit();
+		assert_eq!(db.port("x").unwrap().sequence_number(), 2);
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn panic_safety() {
+		extern crate std;
+
+		use crate::traits::PortAccessors;
+
+		/// A change panicking while it is applied.
+		struct Panicking;
+
+		impl StagedChange for Panicking {
+			fn address(&self) -> usize {
+				usize::MAX
+			}
+
+			fn lock(&self) {}
+
+			fn apply(&mut self) -> Notification {
+				panic!("applying failed");
+			}
+
+			fn unlock(&self) {}
+		}
+
+		let mut db = PortDataBase::default();
+		db.create::<i32>("x", 1).unwrap();
+		let target = db
+			.port("x")
+			.unwrap()
+			.as_in_out_port::<i32>()
+			.unwrap()
+			.value();
+		let mut transaction = Transaction::new(&db);
+		transaction.set::<i32>("x", 2).unwrap();
+		transaction.changes.push(Box::new(Panicking));
+		let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| transaction.commit()));
+		assert!(result.is_err());
+		// the lock acquired before the panic is released
+		assert!(target.try_read().is_some());
+		assert_eq!(db.get::<i32>("x").unwrap(), 2);
+	}
+}
@@ -0,0 +1,410 @@
+// Copyright © 2025 Stephan Kunz
+//! Serialization & deserialization of a [`PortDataBase`], available with feature `serde`.
+
+use core::any::{Any, TypeId};
+
+use alloc::{boxed::Box, collections::btree_map::BTreeMap, format, string::String, vec::Vec};
+use serde::{
+	Deserializer, Serialize, Serializer,
+	de::{self, DeserializeOwned, DeserializeSeed, MapAccess, SeqAccess, Visitor},
+	ser::{self, SerializeMap, SerializeStruct},
+};
+
+use crate::{
+	ConstString,
+	content::{Content, ContentDeserializer},
+	error::{Error, Result},
+	port::Port,
+	port_data_base::PortDataBase,
+	port_value::PortValuePtr,
+};
+
+/// Name of the struct used for tagged values.
+const TAGGED: &str = "PortEntry";
+/// Field names of the struct used for tagged values.
+const FIELDS: &[&str] = &["type", "value"];
+
+/// Function serializing the value of a [`Port`], returns `None` if the port has another type.
+type SerializeFn = fn(&Port) -> Option<Box<dyn erased_serde::Serialize>>;
+/// Function deserializing a value, which is loaded into the [`PortDataBase`] later.
+type LoadFn = fn(&mut dyn erased_serde::Deserializer) -> core::result::Result<Box<dyn LoadedValue>, erased_serde::Error>;
+
+/// A type erased deserialized value, which is loaded after all values have been deserialized.
+trait LoadedValue {
+	/// Checks, that the value can be loaded under `key`.
+	fn check(&self, db: &PortDataBase, key: &str) -> Result<()>;
+
+	/// Loads the value under `key`.
+	fn load(self: Box<Self>, db: &mut PortDataBase, key: ConstString) -> Result<()>;
+}
+
+/// A deserialized value of type `T`.
+struct Loaded<T>(Option<T>);
+
+impl<T: Any + Send + Sync> LoadedValue for Loaded<T> {
+	fn check(&self, db: &PortDataBase, key: &str) -> Result<()> {
+		if let Ok(port) = db.port(key)
+			&& port.as_in_out_port::<T>().is_none()
+		{
+			return Err(port.wrong_type::<T>());
+		}
+		Ok(())
+	}
+
+	fn load(self: Box<Self>, db: &mut PortDataBase, key: ConstString) -> Result<()> {
+		db.load(key, self.0)
+	}
+}
+
+/// Serializable reference to a ports value.
+struct ValueRef<T>(PortValuePtr<T>);
+
+impl<T: Serialize> Serialize for ValueRef<T> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+		self.0.read().as_ref().serialize(serializer)
+	}
+}
+
+/// A registered type.
+#[derive(Clone)]
+struct Entry {
+	/// The tag identifying the type in serialized data.
+	tag: ConstString,
+	/// The [`TypeId`] of the type.
+	type_id: TypeId,
+	/// Serialization function.
+	serialize: SerializeFn,
+	/// Deserialization function.
+	load: LoadFn,
+}
+
+impl Entry {
+	fn new<T: Any + DeserializeOwned + Serialize + Send + Sync>(tag: ConstString) -> Self {
+		Self {
+			tag,
+			type_id: TypeId::of::<T>(),
+			serialize: |port| {
+				port.as_value::<T>()
+					.map(|value| Box::new(ValueRef(value)) as Box<dyn erased_serde::Serialize>)
+			},
+			load: |deserializer| {
+				let value: Option<T> = erased_serde::deserialize(deserializer)?;
+				Ok(Box::new(Loaded(value)))
+			},
+		}
+	}
+}
+
+/// A registry mapping type tags and port names to concrete types,
+/// needed to serialize and deserialize the type erased [`Port`]s of a [`PortDataBase`].
+///
+/// Values of ports registered by name via [`TypeRegistry::register_key`] are serialized as plain values,
+/// all other values are serialized together with their type tag as `{ "type": <tag>, "value": <value> }`.
+///
+/// When deserializing, a value preceding its type tag is buffered until the tag is known.
+/// Enums cannot be buffered, if the data format passes them as enums instead of strings or maps,
+/// so loading such a value fails unless the tag comes first.
+#[derive(Clone, Default)]
+pub struct TypeRegistry {
+	/// The registered types.
+	types: Vec<Entry>,
+	/// The types registered for port names.
+	keys: BTreeMap<ConstString, Entry>,
+}
+
+impl core::fmt::Debug for TypeRegistry {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("TypeRegistry")
+			.field(
+				"types",
+				&self
+					.types
+					.iter()
+					.map(|entry| &entry.tag)
+					.collect::<Vec<_>>(),
+			)
+			.field("keys", &self.keys.keys().collect::<Vec<_>>())
+			.finish()
+	}
+}
+
+impl TypeRegistry {
+	/// Registers type `T` under the type tag `tag`.
+	/// # Errors
+	/// - [`Error::TagAlreadyRegistered`] with the existing tag, if the tag or the type is already registered.
+	pub fn register<T: Any + DeserializeOwned + Serialize + Send + Sync>(
+		&mut self,
+		tag: impl Into<ConstString>,
+	) -> Result<()> {
+		let tag = tag.into();
+		if let Some(entry) = self
+			.types
+			.iter()
+			.find(|entry| entry.tag == tag || entry.type_id == TypeId::of::<T>())
+		{
+			return Err(Error::TagAlreadyRegistered { tag: entry.tag.clone() });
+		}
+		self.types.push(Entry::new::<T>(tag));
+		Ok(())
+	}
+
+	/// Registers type `T` for the port named `key`.
+	/// # Errors
+	/// - [`Error::AlreadyExists`] if the key is already registered.
+	pub fn register_key<T: Any + DeserializeOwned + Serialize + Send + Sync>(
+		&mut self,
+		key: impl Into<ConstString>,
+	) -> Result<()> {
+		let key = key.into();
+		if self.keys.contains_key(&key) {
+			return Err(Error::AlreadyExists { port: key });
+		}
+		self.keys
+			.insert(key.clone(), Entry::new::<T>(key));
+		Ok(())
+	}
+
+	/// Returns the entry for a tag.
+	fn by_tag(&self, tag: &str) -> Option<&Entry> {
+		self.types
+			.iter()
+			.find(|entry| entry.tag.as_ref() == tag)
+	}
+}
+
+/// A serializable view of a [`PortDataBase`] created by [`PortDataBase::serializable`].
+pub struct SerializablePortDataBase<'a> {
+	/// The database.
+	db: &'a PortDataBase,
+	/// The registry.
+	registry: &'a TypeRegistry,
+}
+
+impl core::fmt::Debug for SerializablePortDataBase<'_> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("SerializablePortDataBase")
+			.field("db", &self.db)
+			.field("registry", &self.registry)
+			.finish()
+	}
+}
+
+/// A value together with its type tag.
+struct Tagged<'a> {
+	tag: &'a str,
+	value: &'a dyn erased_serde::Serialize,
+}
+
+impl Serialize for Tagged<'_> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+		let mut state = serializer.serialize_struct(TAGGED, 2)?;
+		state.serialize_field(FIELDS[0], self.tag)?;
+		state.serialize_field(FIELDS[1], self.value)?;
+		state.end()
+	}
+}
+
+impl Serialize for SerializablePortDataBase<'_> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+		let mut map = serializer.serialize_map(None)?;
+		for (key, port) in self.db.entries() {
+			if let Some(entry) = self.registry.keys.get(key) {
+				let value = (entry.serialize)(port)
+					.ok_or_else(|| ser::Error::custom(format!("port '{key}' has not the registered type")))?;
+				map.serialize_entry(key.as_ref(), &*value)?;
+			} else if let Some((entry, value)) = self
+				.registry
+				.types
+				.iter()
+				.find_map(|entry| (entry.serialize)(port).map(|value| (entry, value)))
+			{
+				map.serialize_entry(
+					key.as_ref(),
+					&Tagged {
+						tag: &entry.tag,
+						value: &*value,
+					},
+				)?;
+			} else {
+				return Err(ser::Error::custom(format!("type of port '{key}' is not registered")));
+			}
+		}
+		map.end()
+	}
+}
+
+/// Deserializes a value using the [`LoadFn`] of an [`Entry`].
+struct ValueSeed<'a> {
+	entry: &'a Entry,
+}
+
+impl<'de> DeserializeSeed<'de> for ValueSeed<'_> {
+	type Value = Box<dyn LoadedValue>;
+
+	fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> core::result::Result<Self::Value, D::Error> {
+		let mut erased = <dyn erased_serde::Deserializer>::erase(deserializer);
+		(self.entry.load)(&mut erased).map_err(de::Error::custom)
+	}
+}
+
+/// Deserializes a value together with its type tag.
+struct TaggedSeed<'a> {
+	registry: &'a TypeRegistry,
+	key: ConstString,
+}
+
+impl<'a> TaggedSeed<'a> {
+	fn entry<E: de::Error>(&self, tag: &str) -> core::result::Result<&'a Entry, E> {
+		self.registry
+			.by_tag(tag)
+			.ok_or_else(|| de::Error::custom(format!("type '{tag}' of port '{}' is not registered", self.key)))
+	}
+}
+
+impl<'de> DeserializeSeed<'de> for TaggedSeed<'_> {
+	type Value = Box<dyn LoadedValue>;
+
+	fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> core::result::Result<Self::Value, D::Error> {
+		deserializer.deserialize_struct(TAGGED, FIELDS, self)
+	}
+}
+
+impl<'de> Visitor<'de> for TaggedSeed<'_> {
+	type Value = Box<dyn LoadedValue>;
+
+	fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+		formatter.write_str("a type tag followed by a value")
+	}
+
+	fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> core::result::Result<Self::Value, A::Error> {
+		let tag: String = seq
+			.next_element()?
+			.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+		let entry = self.entry(&tag)?;
+		seq.next_element_seed(ValueSeed { entry })?
+			.ok_or_else(|| de::Error::invalid_length(1, &"a type tag followed by a value"))
+	}
+
+	/// The fields are accepted in any order, a value preceding the type tag is buffered.
+	fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> core::result::Result<Self::Value, A::Error> {
+		let mut tag: Option<String> = None;
+		let mut value: Option<Box<dyn LoadedValue>> = None;
+		let mut buffered: Option<Content> = None;
+		while let Some(field) = map.next_key::<String>()? {
+			if field == FIELDS[0] {
+				if tag.is_some() {
+					return Err(de::Error::duplicate_field(FIELDS[0]));
+				}
+				tag = Some(map.next_value()?);
+			} else if field == FIELDS[1] {
+				if value.is_some() || buffered.is_some() {
+					return Err(de::Error::duplicate_field(FIELDS[1]));
+				}
+				if let Some(tag) = &tag {
+					value = Some(map.next_value_seed(ValueSeed { entry: self.entry(tag)? })?);
+				} else {
+					buffered = Some(map.next_value()?);
+				}
+			} else {
+				return Err(de::Error::unknown_field(&field, FIELDS));
+			}
+		}
+		let tag = tag.ok_or_else(|| de::Error::missing_field(FIELDS[0]))?;
+		match (value, buffered) {
+			(Some(value), _) => Ok(value),
+			(None, Some(content)) => ValueSeed {
+				entry: self.entry(&tag)?,
+			}
+			.deserialize(ContentDeserializer::new(content)),
+			(None, None) => Err(de::Error::missing_field(FIELDS[1])),
+		}
+	}
+}
+
+/// Deserializes the entries of a [`PortDataBase`], a later entry for the same key replaces an earlier one.
+struct Loader<'a> {
+	registry: &'a TypeRegistry,
+}
+
+impl<'de> Visitor<'de> for Loader<'_> {
+	type Value = BTreeMap<ConstString, Box<dyn LoadedValue>>;
+
+	fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+		formatter.write_str("a map of port names to values")
+	}
+
+	fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> core::result::Result<Self::Value, A::Error> {
+		let mut values = BTreeMap::new();
+		while let Some(key) = map.next_key::<String>()? {
+			let key: ConstString = key.into();
+			let value = if let Some(entry) = self.registry.keys.get(&key) {
+				map.next_value_seed(ValueSeed { entry })?
+			} else {
+				map.next_value_seed(TaggedSeed {
+					registry: self.registry,
+					key: key.clone(),
+				})?
+			};
+			values.insert(key, value);
+		}
+		Ok(values)
+	}
+}
+
+impl PortDataBase {
+	/// Returns a serializable view of the [`PortDataBase`] using the types of the `registry`.
+	#[must_use]
+	pub const fn serializable<'a>(&'a self, registry: &'a TypeRegistry) -> SerializablePortDataBase<'a> {
+		SerializablePortDataBase { db: self, registry }
+	}
+
+	/// Loads values from a `deserializer` using the types of the `registry`.
+	/// Existing [`Port`]s are updated, missing ones are created.
+	/// The values are loaded only after all of them have been deserialized and checked,
+	/// so the [`PortDataBase`] is left unchanged if loading fails.
+	/// # Errors
+	/// - if the data cannot be deserialized or does not match the types of existing [`Port`]s.
+	pub fn load_from<'de, D: Deserializer<'de>>(
+		&mut self,
+		registry: &TypeRegistry,
+		deserializer: D,
+	) -> core::result::Result<(), D::Error> {
+		let values = deserializer.deserialize_map(Loader { registry })?;
+		for (key, value) in &values {
+			value
+				.check(self, key)
+				.map_err(de::Error::custom)?;
+		}
+		for (key, value) in values {
+			value.load(self, key).map_err(de::Error::custom)?;
+		}
+		Ok(())
+	}
+
+	/// Creates a [`PortDataBase`] from a `deserializer` using the types of the `registry`.
+	/// # Errors
+	/// - if the data cannot be deserialized.
+	pub fn deserialize_with<'de, D: Deserializer<'de>>(
+		registry: &TypeRegistry,
+		deserializer: D,
+	) -> core::result::Result<Self, D::Error> {
+		let mut db = Self::default();
+		db.load_from(registry, deserializer)?;
+		Ok(db)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	// check, that the auto traits are available.
+	#[test]
+	const fn normal_types() {
+		is_normal::<&TypeRegistry>();
+		is_normal::<TypeRegistry>();
+		is_normal::<SerializablePortDataBase>();
+	}
+}
@@ -0,0 +1,85 @@
+// Copyright © 2025 Stephan Kunz
+//! Blocking wait for changes of a ports value, available with feature `std`.
+
+extern crate std;
+
+use core::{
+	pin::Pin,
+	task::{Context, Poll, Waker},
+	time::Duration,
+};
+
+use alloc::sync::Arc;
+use std::{
+	sync::{Condvar, Mutex, PoisonError},
+	task::Wake,
+	time::Instant,
+};
+
+use crate::{
+	ConstString,
+	error::{Error, Result},
+	port_changed::PortChanged,
+};
+
+/// A [`Waker`] signaling a condition variable.
+#[derive(Default)]
+struct Signal {
+	/// Flag, whether the waker has been woken.
+	woken: Mutex<bool>,
+	/// Condition variable to wait on.
+	condvar: Condvar,
+}
+
+impl Wake for Signal {
+	fn wake(self: Arc<Self>) {
+		self.wake_by_ref();
+	}
+
+	fn wake_by_ref(self: &Arc<Self>) {
+		*self
+			.woken
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner) = true;
+		self.condvar.notify_all();
+	}
+}
+
+/// Blocks the current thread until the `future` resolves or the `timeout` elapsed.
+/// A `timeout` beyond the representable time, like [`Duration::MAX`], waits without a deadline.
+/// # Errors
+/// - [`Error::Timeout`] if the timeout elapsed.
+pub(crate) fn wait_for<T>(port: ConstString, mut future: PortChanged<T>, timeout: Duration) -> Result<u32> {
+	let deadline = Instant::now().checked_add(timeout);
+	let signal = Arc::new(Signal::default());
+	let waker = Waker::from(signal.clone());
+	let mut cx = Context::from_waker(&waker);
+	loop {
+		if let Poll::Ready(sequence_number) = Pin::new(&mut future).poll(&mut cx) {
+			return Ok(sequence_number);
+		}
+		let mut woken = signal
+			.woken
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner);
+		while !*woken {
+			woken = if let Some(deadline) = deadline {
+				let now = Instant::now();
+				if now >= deadline {
+					return Err(Error::Timeout { port });
+				}
+				signal
+					.condvar
+					.wait_timeout(woken, deadline - now)
+					.unwrap_or_else(PoisonError::into_inner)
+					.0
+			} else {
+				signal
+					.condvar
+					.wait(woken)
+					.unwrap_or_else(PoisonError::into_inner)
+			};
+		}
+		*woken = false;
+	}
+}
@@ -62,3 +62,222 @@ fn binding() {
 	test_binding!(MyStruct, p4_name.as_str(), MyStruct::default());
 	test_binding!(Vec<i32>, p4_name.as_str(), vec![1, 2, 3]);
 }
+
+#[test]
+fn unbinding_and_rebinding() {
+	let op1 = OutBoundPort::<i32>::with_value("op1", 42);
+	let op2 = OutBoundPort::<i32>::with_value("op2", 24);
+	let iop = InOutBoundPort::<i32>::with_value("iop", 0);
+	let mut ip = InBoundPort::<i32>::new("ip");
+	assert!(!ip.is_bound());
+	assert!(ip.bound_source_name().is_none());
+	assert!(ip.unbind().is_err());
+
+	assert!(ip.bind_to_out_port(&op1).is_ok());
+	assert!(ip.is_bound());
+	assert_eq!(ip.bound_source_name().unwrap().as_ref(), "op1");
+	assert_eq!(ip.get().unwrap(), 42);
+	assert!(ip.bind_to_out_port(&op2).is_err());
+	assert!(ip.bind_to_in_out_port(&iop).is_err());
+
+	ip.rebind_to_out_port(&op2);
+	assert_eq!(ip.bound_source_name().unwrap().as_ref(), "op2");
+	assert_eq!(ip.get().unwrap(), 24);
+
+	assert!(ip.unbind().is_ok());
+	assert!(!ip.is_bound());
+	assert!(ip.get().is_none());
+	assert_eq!(ip.sequence_number(), 0);
+
+	// an in/out port with own value can be bound
+	let mut iop2 = InOutBoundPort::<i32>::with_value("iop2", 1);
+	assert!(!iop2.is_bound());
+	assert!(iop2.bind_to_in_out_port(&iop).is_ok());
+	assert_eq!(iop2.get().unwrap(), 0);
+	iop2.rebind_to_out_port(&op1);
+	assert_eq!(iop2.get().unwrap(), 42);
+	assert!(iop2.unbind().is_ok());
+	assert!(iop2.get().is_none());
+}
+
+#[test]
+fn change_notification() {
+	use std::sync::{Arc, Mutex};
+
+	let changes: Arc<Mutex<Vec<(String, u32)>>> = Arc::new(Mutex::new(Vec::new()));
+
+	let op1 = OutBoundPort::<i32>::new("op1");
+	let op2 = OutBoundPort::<i32>::new("op2");
+	let mut ip = InBoundPort::<i32>::new("ip");
+	let recorder = changes.clone();
+	// registration before binding, the callback follows the binding
+	ip.on_change(move |name, sequence_number| {
+		recorder
+			.lock()
+			.unwrap()
+			.push((name.to_string(), sequence_number));
+	});
+	assert!(ip.bind_to_out_port(&op1).is_ok());
+
+	op1.set(1);
+	*op1.write().unwrap() = 2;
+	// unmodified guard does not notify
+	drop(op1.write().unwrap());
+	// unrelated port does not notify
+	op2.set(3);
+	assert_eq!(
+		*changes.lock().unwrap(),
+		vec![(String::from("ip"), 1), (String::from("ip"), 2)]
+	);
+
+	ip.rebind_to_out_port(&op2);
+	op1.set(4);
+	op2.set(5);
+	assert_eq!(changes.lock().unwrap().last().unwrap(), &(String::from("ip"), 2));
+
+	// callback may access the port
+	let iop = Arc::new(InOutBoundPort::<i32>::with_value("iop", 0));
+	let port = iop.clone();
+	let seen = changes.clone();
+	iop.on_change(move |name, _| {
+		let value = port.get().unwrap_or_default();
+		seen.lock()
+			.unwrap()
+			.push((name.to_string(), value as u32));
+	});
+	iop.set(7);
+	assert_eq!(iop.replace(8).unwrap(), 7);
+	assert_eq!(iop.take().unwrap(), 8);
+	let changes = changes.lock().unwrap();
+	assert_eq!(
+		changes[changes.len() - 3..],
+		[
+			(String::from("iop"), 7),
+			(String::from("iop"), 8),
+			(String::from("iop"), 0)
+		]
+	);
+}
+
+#[test]
+fn converting_bindings() {
+	use std::sync::{Arc, Mutex};
+
+	let outputs = port_list![out single: f32, out count: i32 = 7, out text: String];
+	let inputs = port_list![in double: f64, in label: String, in length: usize];
+	let changes: Arc<Mutex<Vec<u32>>> = Arc::new(Mutex::new(Vec::new()));
+	let recorder = changes.clone();
+	inputs
+		.on_change::<f64>("double", move |_, sequence_number| {
+			recorder.lock().unwrap().push(sequence_number);
+		})
+		.unwrap();
+
+	// builtin conversions
+	inputs
+		.bind_to_converted::<f32, f64>("double", &outputs, "single")
+		.unwrap();
+	inputs
+		.bind_to_converted::<i32, String>("label", &outputs, "count")
+		.unwrap();
+	assert!(inputs.get::<f64>("double").is_err());
+	assert_eq!(inputs.get::<String>("label").unwrap(), "7");
+	assert_eq!(inputs.sequence_number("label").unwrap(), 1);
+
+	outputs.set::<f32>("single", 1.5).unwrap();
+	outputs.set::<f32>("single", 2.5).unwrap();
+	assert_eq!(inputs.get::<f64>("double").unwrap(), 2.5);
+	assert_eq!(inputs.sequence_number("double").unwrap(), 2);
+	assert_eq!(*changes.lock().unwrap(), vec![1, 2]);
+
+	// a held read guard keeps the former value, but readers see the new one
+	let guard = inputs.read::<f64>("double").unwrap();
+	outputs.set::<f32>("single", 3.5).unwrap();
+	assert_eq!(inputs.get::<f64>("double").unwrap(), 3.5);
+	assert_eq!(inputs.sequence_number("double").unwrap(), 3);
+	assert_eq!(*guard, 2.5);
+	drop(guard);
+	assert_eq!(inputs.get::<f64>("double").unwrap(), 3.5);
+
+	// explicit conversion
+	inputs
+		.bind_to_with::<String, usize>("length", &outputs, "text", String::len)
+		.unwrap();
+	outputs.set::<String>("text", "hello").unwrap();
+	assert_eq!(inputs.get::<usize>("length").unwrap(), 5);
+
+	assert!(matches!(
+		inputs.bind_to_converted::<f32, f64>("double", &outputs, "single"),
+		Err(Error::AlreadyBound { .. })
+	));
+	assert!(matches!(
+		inputs.bind_to_converted::<String, usize>("length", &outputs, "text"),
+		Err(Error::NoConversion { .. })
+	));
+	assert!(matches!(
+		inputs.bind_to_with::<f64, f64>("double", &outputs, "single", |value| *value),
+		Err(Error::WrongType { .. })
+	));
+
+	// unbinding detaches from the source
+	inputs.unbind::<f64>("double").unwrap();
+	outputs.set::<f32>("single", 4.5).unwrap();
+	assert!(inputs.get::<f64>("double").is_err());
+	assert_eq!(changes.lock().unwrap().len(), 3);
+}
+
+#[test]
+fn registered_conversion() {
+	#[derive(Clone, Debug, PartialEq)]
+	struct Celsius(f64);
+	#[derive(Clone, Debug, PartialEq)]
+	struct Fahrenheit(f64);
+
+	Port::register_conversion(|value: &Celsius| Fahrenheit(value.0 * 1.8 + 32.0));
+	let outputs = port_list![out temperature: Celsius = Celsius(100.0)];
+	let mut ip = InBoundPort::<Fahrenheit>::new("temperature");
+	let inputs = PortList::new(vec![Port::from(ip.clone())]);
+	inputs
+		.bind_to_converted::<Celsius, Fahrenheit>("temperature", &outputs, "temperature")
+		.unwrap();
+	assert_eq!(inputs.get::<Fahrenheit>("temperature").unwrap(), Fahrenheit(212.0));
+	outputs
+		.set::<Celsius>("temperature", Celsius(0.0))
+		.unwrap();
+	assert_eq!(inputs.get::<Fahrenheit>("temperature").unwrap(), Fahrenheit(32.0));
+
+	// waiting observes the source
+	#[cfg(feature = "std")]
+	{
+		use std::sync::Arc;
+
+		assert_eq!(
+			inputs
+				.wait_for::<Fahrenheit>("temperature", 1, std::time::Duration::ZERO)
+				.unwrap(),
+			2
+		);
+		let outputs = Arc::new(outputs);
+		let setter = outputs.clone();
+		let handle = std::thread::spawn(move || {
+			std::thread::sleep(std::time::Duration::from_millis(10));
+			setter
+				.set::<Celsius>("temperature", Celsius(-40.0))
+				.unwrap();
+		});
+		assert_eq!(
+			inputs
+				.wait_for::<Fahrenheit>("temperature", 2, std::time::Duration::from_secs(5))
+				.unwrap(),
+			3
+		);
+		handle.join().unwrap();
+		assert_eq!(inputs.get::<Fahrenheit>("temperature").unwrap(), Fahrenheit(-40.0));
+	}
+
+	// the detached clone is not bound
+	assert!(ip.get().is_none());
+	let source = OutBoundPort::<Fahrenheit>::with_value("source", Fahrenheit(PI));
+	ip.bind_to_out_port(&source).unwrap();
+	assert_eq!(ip.get(), Some(Fahrenheit(PI)));
+}
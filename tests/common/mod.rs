@@ -0,0 +1,31 @@
+// Copyright © 2025 Stephan Kunz
+//! Helpers shared by the tests.
+
+use std::{
+	pin::pin,
+	sync::Arc,
+	task::{Context, Poll, Wake},
+	thread::Thread,
+};
+
+/// Wakes a parked thread.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+	fn wake(self: Arc<Self>) {
+		self.0.unpark();
+	}
+}
+
+/// Minimal executor for the tests.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+	let waker = Arc::new(ThreadWaker(std::thread::current())).into();
+	let mut cx = Context::from_waker(&waker);
+	let mut future = pin!(future);
+	loop {
+		match future.as_mut().poll(&mut cx) {
+			Poll::Ready(output) => return output,
+			Poll::Pending => std::thread::park(),
+		}
+	}
+}
@@ -0,0 +1,142 @@
+// Copyright © 2025 Stephan Kunz
+//! Test [`ComputedPort`] features.
+
+use std::sync::{Arc, Mutex};
+
+use dataport::*;
+
+#[derive(Ports)]
+struct Navigator {
+	distance: ComputedPort<f64>,
+}
+
+fn distance(inputs: &[Port]) -> Option<f64> {
+	let pose = inputs[0].get::<(f64, f64)>()?;
+	let goal = inputs[1].get::<(f64, f64)>()?;
+	Some((goal.0 - pose.0).hypot(goal.1 - pose.1))
+}
+
+#[test]
+fn computation() {
+	let sensors = port_list![out pose: (f64, f64), out goal: (f64, f64)];
+	let pose = InBoundPort::<(f64, f64)>::new("pose");
+	let goal = InBoundPort::<(f64, f64)>::new("goal");
+
+	let computed = ComputedPort::new("distance", [Port::from(&pose), Port::from(&goal)], distance);
+	assert_eq!(computed.name().as_ref(), "distance");
+	assert_eq!(computed.inputs().len(), 2);
+	assert!(computed.get().is_none());
+	assert!(matches!(computed.read(), Err(Error::NoValueSet { .. })));
+	assert_eq!(computed.sequence_number(), 0);
+
+	let mut ports = PortList::default();
+	ports.add(Port::from(&pose));
+	ports.add(Port::from(&goal));
+	ports
+		.bind_to::<(f64, f64)>("pose", &sensors, "pose")
+		.unwrap();
+	ports
+		.bind_to::<(f64, f64)>("goal", &sensors, "goal")
+		.unwrap();
+	sensors
+		.set::<(f64, f64)>("pose", (0.0, 0.0))
+		.unwrap();
+	assert!(computed.get().is_none());
+	sensors
+		.set::<(f64, f64)>("goal", (3.0, 4.0))
+		.unwrap();
+	assert_eq!(computed.get(), Some(5.0));
+	assert_eq!(*computed.read().unwrap(), 5.0);
+	assert_eq!(computed.sequence_number(), 1);
+
+	sensors
+		.set::<(f64, f64)>("pose", (3.0, 0.0))
+		.unwrap();
+	assert_eq!(computed.get(), Some(4.0));
+	assert_eq!(computed.sequence_number(), 2);
+
+	// a clone computes independently
+	let clone = computed.clone();
+	assert_eq!(clone.get(), Some(4.0));
+	assert_eq!(clone.sequence_number(), 1);
+}
+
+#[test]
+fn lazy_recomputation() {
+	let count = Arc::new(Mutex::new(0));
+	let counter = count.clone();
+	let mut input = InBoundPort::<i32>::with_default("input", 1);
+	let computed = ComputedPort::new("double", [Port::from(&input)], move |inputs| {
+		*counter.lock().unwrap() += 1;
+		inputs[0].get::<i32>().map(|value| value * 2)
+	});
+	let changes: Arc<Mutex<Vec<u32>>> = Arc::new(Mutex::new(Vec::new()));
+	let recorder = changes.clone();
+	computed.on_change(move |_, sequence_number| {
+		recorder.lock().unwrap().push(sequence_number);
+	});
+	assert_eq!(*count.lock().unwrap(), 0);
+
+	assert_eq!(computed.get(), Some(2));
+	assert_eq!(computed.get(), Some(2));
+	assert_eq!(*count.lock().unwrap(), 1);
+
+	let source = OutBoundPort::<i32>::with_value("source", 5);
+	input.bind_to_out_port(&source).unwrap();
+	assert_eq!(*count.lock().unwrap(), 1);
+	assert_eq!(computed.get(), Some(10));
+	assert_eq!(computed.get(), Some(10));
+	assert_eq!(*count.lock().unwrap(), 2);
+	assert_eq!(*changes.lock().unwrap(), vec![1, 2]);
+}
+
+#[test]
+fn bindings() {
+	let operands = port_list![in a: i32, in b: i32];
+	let computed = ComputedPort::new(
+		"sum",
+		[
+			operands.find("a").unwrap(),
+			operands.find("b").unwrap(),
+		],
+		|inputs| Some(inputs[0].get::<i32>()? + inputs[1].get::<i32>()?),
+	);
+	let navigator = Navigator {
+		distance: ComputedPort::new("distance", [], |_| Some(1.0)),
+	};
+	assert_eq!(navigator.get::<f64>("distance").unwrap(), 1.0);
+
+	let mut sums = PortList::default();
+	sums.add(Port::from(&computed));
+	let inputs = port_list![in sum: i32, in text: String, in converted: i64];
+	inputs
+		.bind_to::<i32>("sum", &sums, "sum")
+		.unwrap();
+	inputs
+		.bind_to_converted::<i32, String>("text", &sums, "sum")
+		.unwrap();
+	inputs
+		.bind_to_converted::<i32, i64>("converted", &sums, "sum")
+		.unwrap();
+	assert!(inputs.get::<i32>("sum").is_err());
+	assert!(inputs.get::<String>("text").is_err());
+
+	operands.set::<i32>("a", 1).unwrap();
+	operands.set::<i32>("b", 2).unwrap();
+	assert_eq!(inputs.get::<i32>("sum").unwrap(), 3);
+	assert_eq!(inputs.get::<String>("text").unwrap(), "3");
+	assert_eq!(inputs.get::<i64>("converted").unwrap(), 3);
+	assert_eq!(sums.get::<i32>("sum").unwrap(), 3);
+
+	operands.set::<i32>("b", 5).unwrap();
+	assert_eq!(inputs.get::<String>("text").unwrap(), "6");
+	assert_eq!(inputs.sequence_number("sum").unwrap(), 2);
+	assert_eq!(computed.get(), Some(6));
+
+	// only in ports can be bound to a computed port
+	let others = port_list![inout sum: i32];
+	assert!(matches!(
+		others.bind_to::<i32>("sum", &sums, "sum"),
+		Err(Error::WrongType { .. })
+	));
+}
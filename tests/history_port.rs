@@ -0,0 +1,71 @@
+// Copyright © 2025 Stephan Kunz
+//! Test history ports.
+
+use dataport::*;
+
+#[derive(Ports)]
+struct Sensor {
+	reading: OutBoundHistoryPort<f64>,
+}
+
+#[derive(Ports)]
+struct Filter {
+	input: InBoundPort<f64>,
+}
+
+#[test]
+fn bound_consumers_see_latest_value() {
+	let sensor = Sensor {
+		reading: OutBoundHistoryPort::new("reading", 3),
+	};
+	let filter = Filter {
+		input: InBoundPort::new("input"),
+	};
+	filter
+		.bind_to::<f64>("input", &sensor, "reading")
+		.unwrap();
+
+	sensor.reading.set(1.0);
+	sensor.set::<f64>("reading", 2.0).unwrap();
+	assert_eq!(filter.input.get(), Some(2.0));
+	assert_eq!(filter.get::<f64>("input").unwrap(), 2.0);
+
+	// writes through the bound port are recorded as well
+	{
+		let mut guard = sensor.write::<f64>("reading").unwrap();
+		*guard = 3.0;
+	}
+	sensor.reading.set(4.0);
+	assert_eq!(
+		sensor.reading.history().collect::<Vec<_>>(),
+		vec![(2, 2.0), (3, 3.0), (4, 4.0)]
+	);
+	assert_eq!(sensor.reading.latest(), Some((4, 4.0)));
+	assert_eq!(
+		sensor
+			.reading
+			.since(filter.input.sequence_number() - 2)
+			.map(|(_, value)| value)
+			.collect::<Vec<_>>(),
+		vec![3.0, 4.0]
+	);
+}
+
+#[test]
+fn typed_binding() {
+	let producer = OutBoundHistoryPort::<i32>::with_value("producer", 2, 1);
+	let mut consumer = InBoundPort::<i32>::new("consumer");
+	consumer.bind_to_history_port(&producer).unwrap();
+	assert_eq!(consumer.get(), Some(1));
+	producer.set(2);
+	assert_eq!(consumer.get(), Some(2));
+
+	let list = PortList::new(vec![
+		Port::create_out_history_port::<i32>("out", 2),
+		Port::create_inout_port::<i32>("inout"),
+	]);
+	list.bind_to::<i32>("inout", &list, "out")
+		.unwrap();
+	list.set::<i32>("inout", 42).unwrap();
+	assert_eq!(list.get::<i32>("out").unwrap(), 42);
+}
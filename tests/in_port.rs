@@ -7,6 +7,9 @@ use std::f64::consts::PI;
 
 use dataport::*;
 
+mod common;
+use common::block_on;
+
 const CONST_NAME: &str = "p2";
 static STATIC_NAME: &str = "p3";
 
@@ -45,3 +48,109 @@ fn getter() {
 	test_getter!(MyStruct, p4_name.as_str(), MyStruct::default());
 	test_getter!(Vec<i32>, p4_name.as_str(), vec![1, 2, 3]);
 }
+
+#[test]
+fn changed() {
+	let op = std::sync::Arc::new(OutBoundPort::<i32>::new("op"));
+	let mut ip = InBoundPort::<i32>::new("ip");
+	assert!(ip.bind_to_out_port(&op).is_ok());
+
+	// already changed since sequence number 0
+	op.set(1);
+	assert_eq!(block_on(ip.wait_for_change(0)), 1);
+
+	let writer = op.clone();
+	let handle = std::thread::spawn(move || {
+		std::thread::sleep(std::time::Duration::from_millis(50));
+		writer.set(2);
+	});
+	assert_eq!(block_on(ip.changed()), 2);
+	assert_eq!(ip.get().unwrap(), 2);
+	handle.join().unwrap();
+
+	let writer = op.clone();
+	let handle = std::thread::spawn(move || {
+		std::thread::sleep(std::time::Duration::from_millis(50));
+		*writer.write().unwrap() = 3;
+	});
+	assert_eq!(block_on(ip.wait_for_change(2)), 3);
+	assert_eq!(ip.get().unwrap(), 3);
+	handle.join().unwrap();
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn wait_for_update() {
+	use std::time::Duration;
+
+	let op = std::sync::Arc::new(OutBoundPort::<i32>::new("op"));
+	let mut ip = InBoundPort::<i32>::new("ip");
+	assert!(ip.bind_to_out_port(&op).is_ok());
+
+	assert!(matches!(
+		ip.wait_for_update(0, Duration::from_millis(10)),
+		Err(Error::Timeout { .. })
+	));
+
+	let writer = op.clone();
+	let handle = std::thread::spawn(move || {
+		std::thread::sleep(Duration::from_millis(50));
+		writer.set(1);
+	});
+	// the maximum duration waits without a deadline
+	assert_eq!(ip.wait_for_update(0, Duration::MAX).unwrap(), 1);
+	handle.join().unwrap();
+	// no waiting if already changed
+	assert_eq!(ip.wait_for_update(0, Duration::ZERO).unwrap(), 1);
+}
+
+#[test]
+fn defaults() {
+	let mut ip = InBoundPort::<f64>::with_default("in", PI);
+	assert_eq!(ip.get(), Some(PI));
+	assert_eq!(*ip.read().unwrap(), PI);
+	assert_eq!(ip.sequence_number(), 0);
+	assert!(!ip.is_required());
+
+	let op = OutBoundPort::<f64>::new("out");
+	ip.bind_to_out_port(&op).unwrap();
+	assert_eq!(ip.get(), None);
+	op.set(1.0);
+	assert_eq!(ip.get(), Some(1.0));
+	// unbinding restores the default
+	ip.unbind().unwrap();
+	assert_eq!(ip.get(), Some(PI));
+	assert_eq!(ip.sequence_number(), 0);
+
+	let list = PortList::new(vec![
+		Port::create_in_port_with_default::<f64>("a", PI),
+		Port::create_in_port::<f64>("b"),
+	]);
+	assert_eq!(list.get::<f64>("a").unwrap(), PI);
+	assert!(list.get::<f64>("b").is_err());
+}
+
+#[test]
+fn required() {
+	let ip = InBoundPort::<f64>::new("in").required();
+	assert!(ip.is_required());
+	let list = PortList::new(vec![
+		Port::from(ip),
+		Port::from(InBoundPort::<i32>::with_default("optional", 42)),
+		Port::from(InBoundPort::<i32>::new("other").required()),
+	]);
+	let Err(Error::RequiredNotBound { ports }) = list.validate() else {
+		panic!("required ports are not reported");
+	};
+	assert_eq!(ports, vec!["in".into(), "other".into()]);
+
+	let outputs = PortList::new(vec![
+		Port::create_out_port::<f64>("out"),
+		Port::create_out_port::<i32>("other"),
+	]);
+	list.bind_to::<f64>("in", &outputs, "out")
+		.unwrap();
+	list.bind_to::<i32>("other", &outputs, "other")
+		.unwrap();
+	assert!(list.validate().is_ok());
+}
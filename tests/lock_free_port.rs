@@ -0,0 +1,211 @@
+// Copyright © 2025 Stephan Kunz
+//! Test lock-free ports.
+
+use std::sync::{
+	Arc,
+	atomic::{AtomicU32, Ordering},
+};
+
+use dataport::*;
+
+mod common;
+use common::block_on;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Pose {
+	x: f64,
+	y: f64,
+	theta: f64,
+}
+
+#[derive(Ports)]
+struct Controller {
+	setpoint: LockFreeOutBoundPort<Pose>,
+}
+
+#[derive(Ports)]
+struct Actuator {
+	setpoint: LockFreeInBoundPort<Pose>,
+}
+
+#[test]
+fn binding_via_accessors() {
+	let controller = Controller {
+		setpoint: LockFreeOutBoundPort::new("setpoint"),
+	};
+	let actuator = Actuator {
+		setpoint: LockFreeInBoundPort::new("setpoint"),
+	};
+	actuator
+		.bind_to::<Pose>("setpoint", &controller, "setpoint")
+		.unwrap();
+	assert!(
+		actuator
+			.bind_to::<Pose>("setpoint", &controller, "setpoint")
+			.is_err()
+	);
+	assert!(
+		actuator
+			.bind_to::<f64>("setpoint", &controller, "setpoint")
+			.is_err()
+	);
+	assert!(actuator.setpoint.is_bound());
+
+	let observed = Arc::new(AtomicU32::new(0));
+	let observer = observed.clone();
+	actuator
+		.setpoint
+		.on_change(move |_, sequence_number| observer.store(sequence_number, Ordering::SeqCst));
+
+	let pose = Pose {
+		x: 1.0,
+		y: 2.0,
+		theta: 0.5,
+	};
+	controller.setpoint.set(pose);
+	assert_eq!(actuator.setpoint.get(), Some(pose));
+	assert_eq!(observed.load(Ordering::SeqCst), 1);
+
+	actuator.unbind::<Pose>("setpoint").unwrap();
+	assert!(actuator.setpoint.get().is_none());
+	controller.setpoint.set(Pose::default());
+	assert_eq!(observed.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn accessors() {
+	let controller = Controller {
+		setpoint: LockFreeOutBoundPort::new("setpoint"),
+	};
+	let actuator = Actuator {
+		setpoint: LockFreeInBoundPort::new("setpoint"),
+	};
+	actuator
+		.bind_to::<Pose>("setpoint", &controller, "setpoint")
+		.unwrap();
+	assert!(matches!(
+		controller.get::<Pose>("setpoint"),
+		Err(Error::ValueNotInitialized { .. })
+	));
+
+	let pose = Pose {
+		x: 1.0,
+		y: 2.0,
+		theta: 0.5,
+	};
+	controller.set::<Pose>("setpoint", pose).unwrap();
+	assert_eq!(controller.get::<Pose>("setpoint").unwrap(), pose);
+	assert_eq!(actuator.get::<Pose>("setpoint").unwrap(), pose);
+	assert_eq!(actuator.sequence_number("setpoint").unwrap(), 1);
+	let port = actuator.find("setpoint").unwrap();
+	assert_eq!(port.get::<Pose>(), Some(pose));
+	#[cfg(feature = "std")]
+	assert_eq!(
+		actuator
+			.wait_for::<Pose>("setpoint", 0, std::time::Duration::from_millis(10))
+			.unwrap(),
+		1
+	);
+
+	// accesses needing a lock on the value are not supported
+	assert!(matches!(actuator.set::<Pose>("setpoint", pose), Err(Error::LockFree { .. })));
+	assert!(matches!(controller.read::<Pose>("setpoint"), Err(Error::LockFree { .. })));
+	assert!(matches!(actuator.try_read::<Pose>("setpoint"), Err(Error::LockFree { .. })));
+	assert!(matches!(controller.write::<Pose>("setpoint"), Err(Error::LockFree { .. })));
+	assert!(matches!(
+		controller.replace::<Pose>("setpoint", pose),
+		Err(Error::LockFree { .. })
+	));
+	assert!(matches!(controller.take::<Pose>("setpoint"), Err(Error::LockFree { .. })));
+	assert!(matches!(
+		actuator.read_many::<(Pose,)>(("setpoint",)),
+		Err(Error::LockFree { .. })
+	));
+	assert!(matches!(controller.get::<f64>("setpoint"), Err(Error::NoValueSet { .. })));
+}
+
+#[test]
+fn concurrent_access() {
+	let writer = LockFreeOutBoundPort::<(u64, u64)>::with_value("writer", (0, 0));
+	let mut reader = LockFreeInBoundPort::<(u64, u64)>::new("reader");
+	reader.bind_to_out_port(&writer).unwrap();
+
+	let handle = {
+		let writer = writer.clone();
+		std::thread::spawn(move || {
+			for value in 1..=10_000 {
+				writer.set((value, value));
+			}
+		})
+	};
+	let mut last = 0;
+	while last < 10_000 {
+		let (first, second) = reader.get().unwrap();
+		// values are never torn and never go back in time
+		assert_eq!(first, second);
+		assert!(first >= last);
+		last = first;
+	}
+	handle.join().unwrap();
+	assert_eq!(reader.sequence_number(), 10_001);
+}
+
+#[test]
+fn rebinding_while_reading() {
+	let sources = PortList::new(vec![
+		Port::from(LockFreeOutBoundPort::<u64>::with_value("first", 1_u64)),
+		Port::from(LockFreeOutBoundPort::<u64>::with_value("second", 2_u64)),
+	]);
+	let reader = LockFreeInBoundPort::<u64>::new("reader");
+	let readers = PortList::new(vec![Port::from(&reader)]);
+	readers
+		.bind_to::<u64>("reader", &sources, "first")
+		.unwrap();
+
+	let handle = std::thread::spawn(move || {
+		for _ in 0..1_000 {
+			readers
+				.rebind_to::<u64>("reader", &sources, "second")
+				.unwrap();
+			readers
+				.rebind_to::<u64>("reader", &sources, "first")
+				.unwrap();
+		}
+		readers.unbind::<u64>("reader").unwrap();
+	});
+	// the value is gone, when the port is unbound at the end
+	while let Some(value) = reader.get() {
+		assert!(value == 1 || value == 2);
+	}
+	handle.join().unwrap();
+	assert!(!reader.is_bound());
+}
+
+#[test]
+fn changed() {
+	let op = LockFreeOutBoundPort::<f64>::new("op");
+	let mut ip = LockFreeInBoundPort::<f64>::new("ip");
+	ip.bind_to_out_port(&op).unwrap();
+
+	// already changed since sequence number 0
+	op.set(1.0);
+	assert_eq!(block_on(ip.wait_for_change(0)), 1);
+
+	let writer = op.clone();
+	let handle = std::thread::spawn(move || {
+		std::thread::sleep(std::time::Duration::from_millis(50));
+		writer.set(2.0);
+	});
+	assert_eq!(block_on(ip.changed()), 2);
+	assert_eq!(ip.get(), Some(2.0));
+	handle.join().unwrap();
+
+	let writer = op.clone();
+	let handle = std::thread::spawn(move || {
+		std::thread::sleep(std::time::Duration::from_millis(50));
+		*writer.write().unwrap() = 3.0;
+	});
+	assert_eq!(block_on(ip.wait_for_change(2)), 3);
+	assert_eq!(*ip.read().unwrap(), 3.0);
+	handle.join().unwrap();
+}
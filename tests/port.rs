@@ -179,3 +179,48 @@ fn port_connections() {
 	test_connections!(String, STATIC_NAME, "hello world");
 	test_connections!(Vec<i32>, p4_name.as_str(), vec![1, 2, 3]);
 }
+
+#[test]
+fn port_introspection() {
+	use core::any::TypeId;
+
+	let ip = Port::create_in_port::<i32>("in");
+	assert_eq!(ip.type_name(), "i32");
+	assert_eq!(ip.value_type_id(), TypeId::of::<i32>());
+	assert_eq!(ip.direction(), PortDirection::In);
+
+	let op = Port::create_out_port::<f64>("out");
+	assert_eq!(op.type_name(), "f64");
+	assert_eq!(op.direction(), PortDirection::Out);
+
+	let iop = Port::create_inout_port::<String>("inout");
+	assert_eq!(iop.type_name(), "alloc::string::String");
+	assert_eq!(iop.value_type_id(), TypeId::of::<String>());
+	assert_eq!(iop.direction(), PortDirection::InOut);
+
+	let qp = Port::create_in_queue_port::<u8>("queue");
+	assert_eq!(qp.value_type_id(), TypeId::of::<u8>());
+	assert_eq!(qp.direction(), PortDirection::In);
+
+	// wrong type binding reports both types
+	let list = port_list![in input: i32, out output: f64];
+	match list.bind_to::<i32>("input", &list, "output") {
+		Err(Error::WrongType { port, expected, actual }) => {
+			assert_eq!(port.as_ref(), "output");
+			assert_eq!(expected, "i32");
+			assert_eq!(actual, "f64");
+		}
+		_ => panic!("binding must fail with WrongType"),
+	}
+	let db = {
+		let mut db = PortDataBase::default();
+		db.create::<i32>("key", 1).unwrap();
+		db
+	};
+	assert_eq!(
+		db.update::<f64>("key", 2.0)
+			.unwrap_err()
+			.to_string(),
+		"port: 'key' has type 'i32' instead of the wanted type 'f64'"
+	);
+}
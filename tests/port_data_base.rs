@@ -57,3 +57,168 @@ fn access() {
 
 	assert!(pb.delete::<i32>("test").is_ok());
 }
+
+#[test]
+fn change_notification() {
+	use std::sync::{
+		Arc,
+		atomic::{AtomicU32, Ordering},
+	};
+
+	let mut pb = PortDataBase::default();
+	let last = Arc::new(AtomicU32::new(0));
+	assert!(pb.on_change::<i32>("test", |_, _| {}).is_err());
+	assert!(pb.create::<i32>("test", 42).is_ok());
+	assert!(pb.on_change::<f64>("test", |_, _| {}).is_err());
+	let recorder = last.clone();
+	assert!(
+		pb.on_change::<i32>("test", move |name, sequence_number| {
+			assert_eq!(name, "test");
+			recorder.store(sequence_number, Ordering::Relaxed);
+		})
+		.is_ok()
+	);
+	assert!(pb.set::<i32>("test", 24).is_ok());
+	assert_eq!(last.load(Ordering::Relaxed), 2);
+	assert!(pb.update::<i32>("test", 12).is_ok());
+	assert_eq!(last.load(Ordering::Relaxed), 3);
+	*pb.write::<i32>("test").unwrap() = 6;
+	assert_eq!(last.load(Ordering::Relaxed), 4);
+	assert!(pb.delete::<i32>("test").is_ok());
+	assert_eq!(last.load(Ordering::Relaxed), 5);
+}
+
+#[test]
+fn transactions() {
+	use std::sync::{Arc, Mutex};
+
+	let mut pb = PortDataBase::default();
+	pb.create::<f64>("x", 0.0).unwrap();
+	pb.create::<f64>("y", 0.0).unwrap();
+	pb.create::<f64>("theta", 0.0).unwrap();
+	let pb = Arc::new(pb);
+
+	// observers see all changes of a transaction
+	let seen: Arc<Mutex<Vec<(f64, f64)>>> = Arc::new(Mutex::new(Vec::new()));
+	let recorder = seen.clone();
+	let observed = pb.clone();
+	pb.on_change::<f64>("x", move |_, _| {
+		let pose = (observed.get::<f64>("x").unwrap(), observed.get::<f64>("y").unwrap());
+		recorder.lock().unwrap().push(pose);
+	})
+	.unwrap();
+
+	let result = pb.transaction(|tx| {
+		tx.set::<f64>("x", 1.0)?;
+		tx.set::<f64>("y", 2.0)?;
+		tx.set::<f64>("theta", PI)?;
+		Ok(42)
+	});
+	assert_eq!(result.unwrap(), 42);
+	assert_eq!(*seen.lock().unwrap(), vec![(1.0, 2.0)]);
+	assert_eq!(pb.get::<f64>("theta").unwrap(), PI);
+	assert_eq!(pb.sequence_number("x").unwrap(), 2);
+	assert_eq!(pb.sequence_number("theta").unwrap(), 2);
+
+	// a failing transaction changes nothing
+	let result = pb.transaction(|tx| {
+		tx.set::<f64>("x", 3.0)?;
+		tx.set::<i32>("y", 4)?;
+		Ok(())
+	});
+	assert!(matches!(result, Err(Error::WrongType { .. })));
+	assert!(matches!(
+		pb.transaction(|tx| tx.set::<f64>("z", 0.0)),
+		Err(Error::NotFound { .. })
+	));
+	assert_eq!(pb.get::<f64>("x").unwrap(), 1.0);
+	assert_eq!(pb.sequence_number("x").unwrap(), 2);
+	assert_eq!(seen.lock().unwrap().len(), 1);
+
+	// concurrent transactions in different order do not deadlock
+	let handles: Vec<_> = (0..4)
+		.map(|i| {
+			let pb = pb.clone();
+			std::thread::spawn(move || {
+				for n in 0..100 {
+					let value = f64::from(n);
+					pb.transaction(|tx| {
+						if i % 2 == 0 {
+							tx.set::<f64>("x", value)?;
+							tx.set::<f64>("y", value)
+						} else {
+							tx.set::<f64>("y", value)?;
+							tx.set::<f64>("x", value)
+						}
+					})
+					.unwrap();
+				}
+			})
+		})
+		.collect();
+	for handle in handles {
+		handle.join().unwrap();
+	}
+	assert_eq!(pb.sequence_number("x").unwrap(), 402);
+	assert_eq!(pb.get::<f64>("x").unwrap(), 99.0);
+	assert_eq!(pb.get::<f64>("y").unwrap(), 99.0);
+}
+
+#[test]
+fn checkpoints() {
+	#[derive(Clone, Debug, PartialEq)]
+	struct Pose {
+		x: f64,
+		y: f64,
+	}
+
+	struct Handle(i32);
+
+	PortDataBase::register_checkpoint_type::<Pose>();
+	let mut pb = PortDataBase::default();
+	pb.create::<i32>("count", 1).unwrap();
+	pb.create::<String>("name", "start").unwrap();
+	pb.create::<Pose>("pose", Pose { x: 0.0, y: 0.0 })
+		.unwrap();
+	pb.create::<Vec<f64>>("path", vec![1.0, 2.0])
+		.unwrap();
+	pb.create::<Handle>("handle", Handle(1)).unwrap();
+	let checkpoint = pb.checkpoint();
+
+	pb.update::<i32>("count", 2).unwrap();
+	pb.update::<Pose>("pose", Pose { x: 1.0, y: 2.0 })
+		.unwrap();
+	pb.delete::<String>("name").unwrap();
+	pb.delete::<Vec<f64>>("path").unwrap();
+	pb.create::<i32>("path", 3).unwrap();
+	pb.create::<f64>("created", PI).unwrap();
+	pb.update::<Handle>("handle", Handle(2)).unwrap();
+
+	pb.restore(&checkpoint);
+	assert_eq!(pb.get::<i32>("count").unwrap(), 1);
+	assert_eq!(pb.sequence_number("count").unwrap(), 3);
+	assert_eq!(pb.get::<String>("name").unwrap(), "start");
+	assert_eq!(pb.get::<Pose>("pose").unwrap(), Pose { x: 0.0, y: 0.0 });
+	assert_eq!(pb.get::<Vec<f64>>("path").unwrap(), vec![1.0, 2.0]);
+	assert!(!pb.contains_key("created"));
+	// values which cannot be copied are left unchanged
+	assert_eq!(pb.read::<Handle>("handle").unwrap().0, 2);
+
+	// unchanged values keep their sequence number
+	let count = pb.sequence_number("count").unwrap();
+	let pose = pb.sequence_number("pose").unwrap();
+	pb.update::<i32>("count", 4).unwrap();
+	pb.restore(&checkpoint);
+	assert_eq!(pb.get::<i32>("count").unwrap(), 1);
+	assert_eq!(pb.sequence_number("count").unwrap(), count + 2);
+	assert_eq!(pb.sequence_number("pose").unwrap(), pose);
+
+	// a recreated value is replaced, even if it has the same sequence number
+	let mut pb = PortDataBase::default();
+	pb.create::<i32>("a", 1).unwrap();
+	let checkpoint = pb.checkpoint();
+	pb.delete::<i32>("a").unwrap();
+	pb.create::<i32>("a", 5).unwrap();
+	pb.restore(&checkpoint);
+	assert_eq!(pb.get::<i32>("a").unwrap(), 1);
+}
@@ -0,0 +1,121 @@
+// Copyright © 2025 Stephan Kunz
+//! Test [`PortExport`] of port bindings.
+
+use std::f64::consts::PI;
+
+use dataport::*;
+
+#[derive(Ports)]
+struct Sensor {
+	reading: OutBoundPort<f64>,
+}
+
+#[derive(Ports)]
+struct Controller {
+	input: InBoundPort<f64>,
+	output: OutBoundPort<String>,
+}
+
+#[test]
+fn export() {
+	let sensor = Sensor {
+		reading: OutBoundPort::new("reading"),
+	};
+	let controller = Controller {
+		input: InBoundPort::new("input"),
+		output: OutBoundPort::new("output"),
+	};
+	controller
+		.bind_to::<f64>("input", &sensor, "reading")
+		.unwrap();
+	sensor.reading.set(PI);
+
+	let mut export = PortExport::default();
+	export.add_provider("sensor", &sensor).unwrap();
+	export
+		.add_provider("controller", &controller)
+		.unwrap();
+
+	let dot = export.dot().to_string();
+	assert!(dot.starts_with("digraph ports {\n"));
+	assert!(dot.contains("\t\t\"sensor.reading\" [label=\"reading\\nout f64\\n#1\"];\n"));
+	assert!(dot.contains("\t\t\"controller.output\" [label=\"output\\nout alloc::string::String\\n#0\"];\n"));
+	assert!(dot.contains("\t\"sensor.reading\" -> \"controller.input\";\n"));
+
+	let json: serde_json::Value = serde_json::from_str(&export.json().to_string()).unwrap();
+	assert_eq!(
+		json,
+		serde_json::json!({
+			"providers": [
+				{
+					"name": "sensor",
+					"ports": [
+						{ "name": "reading", "direction": "out", "type": "f64", "sequence_number": 1 },
+					],
+				},
+				{
+					"name": "controller",
+					"ports": [
+						{ "name": "input", "direction": "in", "type": "f64", "sequence_number": 1 },
+						{ "name": "output", "direction": "out", "type": "alloc::string::String", "sequence_number": 0 },
+					],
+				},
+			],
+			"edges": [
+				{
+					"from": { "provider": "sensor", "port": "reading" },
+					"to": { "provider": "controller", "port": "input" },
+				},
+			],
+		})
+	);
+}
+
+#[test]
+fn chained_bindings() {
+	let portlist1 = PortList::new(vec![
+		Port::create_out_port::<f64>("p1c"),
+		Port::create_inout_port::<f64>("p1d"),
+	]);
+	let portlist2 = PortList::new(vec![
+		Port::create_inout_port::<f64>("p2c"),
+		Port::create_in_port::<f64>("p2d"),
+	]);
+	// create p1c -> p2c -> p1d -> p2d
+	portlist2
+		.bind_to::<f64>("p2c", &portlist1, "p1c")
+		.unwrap();
+	portlist1
+		.bind_to::<f64>("p1d", &portlist2, "p2c")
+		.unwrap();
+	portlist2
+		.bind_to::<f64>("p2d", &portlist1, "p1d")
+		.unwrap();
+
+	let mut export = PortExport::default();
+	export.add_provider("n1", &portlist1).unwrap();
+	export.add_provider("n2", &portlist2).unwrap();
+	let json: serde_json::Value = serde_json::from_str(&export.json().to_string()).unwrap();
+	let edges = json["edges"]
+		.as_array()
+		.unwrap()
+		.iter()
+		.map(|edge| {
+			format!(
+				"{}.{} -> {}.{}",
+				edge["from"]["provider"].as_str().unwrap(),
+				edge["from"]["port"].as_str().unwrap(),
+				edge["to"]["provider"].as_str().unwrap(),
+				edge["to"]["port"].as_str().unwrap()
+			)
+		})
+		.collect::<Vec<_>>();
+	assert_eq!(
+		edges,
+		[
+			"n2.p2c -> n1.p1d",
+			"n1.p1c -> n2.p2c",
+			"n1.p1d -> n2.p2d"
+		]
+	);
+}
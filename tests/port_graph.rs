@@ -0,0 +1,158 @@
+// Copyright © 2025 Stephan Kunz
+//! Test [`PortGraph`] validation.
+
+use std::f64::consts::PI;
+
+use dataport::*;
+
+#[derive(Ports)]
+struct Sensor {
+	reading: OutBoundPort<f64>,
+	status: OutBoundPort<i32>,
+}
+
+#[derive(Ports)]
+struct Controller {
+	input: InBoundPort<f64>,
+	#[port(name = "state")]
+	status: InBoundPort<i32>,
+	output: OutBoundPort<f64>,
+}
+
+#[test]
+fn valid_graph() {
+	let sensor = Sensor {
+		reading: OutBoundPort::new("reading"),
+		status: OutBoundPort::new("status"),
+	};
+	let controller = Controller {
+		input: InBoundPort::new("input"),
+		status: InBoundPort::new("state"),
+		output: OutBoundPort::new("output"),
+	};
+
+	let mut graph = PortGraph::default();
+	graph.add_node("sensor", &sensor).unwrap();
+	graph.add_node("controller", &controller).unwrap();
+	assert!(graph.add_node("sensor", &sensor).is_err());
+	graph.bind_to::<f64>("controller", "input", "sensor", "reading");
+	graph.bind_to::<i32>("controller", "state", "sensor", "status");
+	assert!(graph.validate().is_ok());
+
+	assert!(graph.bind().is_ok());
+	sensor.reading.set(PI);
+	assert_eq!(controller.input.get(), Some(PI));
+	assert!(controller.status.is_bound());
+
+	// now the input ports are already bound
+	assert_eq!(graph.validate().issues().len(), 2);
+}
+
+#[test]
+fn invalid_graph() {
+	let sensor = Sensor {
+		reading: OutBoundPort::new("reading"),
+		status: OutBoundPort::new("status"),
+	};
+	let controller = Controller {
+		input: InBoundPort::new("input"),
+		status: InBoundPort::new("state"),
+		output: OutBoundPort::new("output"),
+	};
+
+	let mut graph = PortGraph::default();
+	graph.add_node("sensor", &sensor).unwrap();
+	graph.add_node("controller", &controller).unwrap();
+	graph.bind_to::<f64>("controller", "input", "sensor", "reading");
+	graph.bind_to::<f64>("controller", "input", "sensor", "status");
+	graph.bind_to::<f64>("sensor", "reading", "controller", "output");
+	graph.bind_to::<f64>("actuator", "input", "controller", "output");
+	graph.bind_to::<f64>("controller", "unknown", "controller", "output");
+
+	let report = graph.bind();
+	assert!(!report.is_ok());
+	let issues = report.issues();
+	assert_eq!(issues.len(), 6);
+	assert!(matches!(
+		&issues[0],
+		GraphIssue::WrongType { node, port, expected: "f64", actual: "i32" }
+			if node.as_ref() == "sensor" && port.as_ref() == "status"
+	));
+	assert!(matches!(
+		&issues[1],
+		GraphIssue::WrongDirection { node, port } if node.as_ref() == "sensor" && port.as_ref() == "reading"
+	));
+	assert!(matches!(&issues[2], GraphIssue::NodeNotFound { node } if node.as_ref() == "actuator"));
+	assert!(matches!(
+		&issues[3],
+		GraphIssue::PortNotFound { node, port } if node.as_ref() == "controller" && port.as_ref() == "unknown"
+	));
+	assert!(matches!(
+		&issues[4],
+		GraphIssue::BoundTwice { node, port } if node.as_ref() == "controller" && port.as_ref() == "input"
+	));
+	assert!(matches!(
+		&issues[5],
+		GraphIssue::Unbound { node, port } if node.as_ref() == "controller" && port.as_ref() == "state"
+	));
+	// nothing has been bound
+	assert!(!controller.input.is_bound());
+}
+
+#[test]
+fn cycles() {
+	let portlist1 = PortList::new(vec![
+		Port::create_out_port::<f64>("p1c"),
+		Port::create_inout_port::<f64>("p1d"),
+	]);
+	let portlist2 = PortList::new(vec![
+		Port::create_inout_port::<f64>("p2c"),
+		Port::create_in_port::<f64>("p2d"),
+	]);
+
+	let mut graph = PortGraph::default();
+	graph.add_node("n1", &portlist1).unwrap();
+	graph.add_node("n2", &portlist2).unwrap();
+	// p1c -> p2c -> p1d -> p2d
+	graph.bind_to::<f64>("n2", "p2c", "n1", "p1c");
+	graph.bind_to::<f64>("n1", "p1d", "n2", "p2c");
+	graph.bind_to::<f64>("n2", "p2d", "n1", "p1d");
+	assert!(graph.validate().is_ok());
+
+	// p1d -> p2c closes a cycle
+	graph.bind_to::<f64>("n2", "p2c", "n1", "p1d");
+	let report = graph.validate();
+	let cycles = report
+		.issues()
+		.iter()
+		.filter_map(|issue| match issue {
+			GraphIssue::Cycle { ports } => Some(
+				ports
+					.iter()
+					.map(|(node, port)| format!("{node}.{port}"))
+					.collect::<Vec<_>>(),
+			),
+			_ => None,
+		})
+		.collect::<Vec<_>>();
+	assert_eq!(cycles, vec![vec!["n2.p2c", "n1.p1d"]]);
+}
+
+#[test]
+fn defaulted_inputs() {
+	let controller = Controller {
+		input: InBoundPort::with_default("input", 1.0),
+		status: InBoundPort::with_default("state", 0).required(),
+		output: OutBoundPort::new("output"),
+	};
+
+	let mut graph = PortGraph::default();
+	graph.add_node("controller", &controller).unwrap();
+	// an optional input with a default may stay unbound, a required one not
+	let report = graph.validate();
+	assert_eq!(report.issues().len(), 1);
+	assert!(matches!(
+		&report.issues()[0],
+		GraphIssue::Unbound { node, port } if node.as_ref() == "controller" && port.as_ref() == "state"
+	));
+}
@@ -93,3 +93,163 @@ fn bind_get_and_set() {
 	assert_eq!(portlist1.get::<f64>("p1d").unwrap(), PI);
 	assert_eq!(portlist2.get::<f64>("p2d").unwrap(), PI);
 }
+
+#[test]
+fn unbind_and_rebind() {
+	let portlist1 = PortList::new(vec![
+		Port::create_out_port::<i32>("p1a"),
+		Port::create_out_port::<i32>("p1b"),
+	]);
+
+	let portlist2 = PortList::new(vec![
+		Port::create_in_port::<i32>("p2a"),
+		Port::create_inout_port::<i32>("p2b"),
+	]);
+
+	assert!(portlist2.unbind::<i32>("p2a").is_err());
+	assert!(portlist2.unbind::<i32>("p2x").is_err());
+	assert!(!portlist2.find("p2a").unwrap().is_bound());
+
+	assert!(
+		portlist2
+			.bind_to::<i32>("p2a", &portlist1, "p1a")
+			.is_ok()
+	);
+	assert!(
+		portlist2
+			.bind_to::<i32>("p2a", &portlist1, "p1b")
+			.is_err()
+	);
+	assert!(
+		portlist2
+			.bind_to::<f64>("p2b", &portlist1, "p1b")
+			.is_err()
+	);
+	assert!(
+		portlist2
+			.bind_to::<i32>("p2b", &portlist1, "p1b")
+			.is_ok()
+	);
+
+	let p2a = portlist2.find("p2a").unwrap();
+	assert!(p2a.is_bound());
+	assert_eq!(p2a.bound_source_name().unwrap().as_ref(), "p1a");
+
+	assert!(portlist1.set::<i32>("p1a", 1).is_ok());
+	assert!(portlist1.set::<i32>("p1b", 2).is_ok());
+	assert_eq!(portlist2.get::<i32>("p2a").unwrap(), 1);
+	assert_eq!(portlist2.get::<i32>("p2b").unwrap(), 2);
+
+	assert!(
+		portlist2
+			.rebind_to::<i32>("p2a", &portlist1, "p1b")
+			.is_ok()
+	);
+	assert_eq!(p2a.bound_source_name().unwrap().as_ref(), "p1b");
+	assert_eq!(portlist2.get::<i32>("p2a").unwrap(), 2);
+
+	assert!(portlist2.unbind::<f64>("p2a").is_err());
+	assert!(portlist2.unbind::<i32>("p2a").is_ok());
+	assert!(!p2a.is_bound());
+	assert!(portlist2.get::<i32>("p2a").is_err());
+	assert!(portlist2.unbind::<i32>("p2a").is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn wait_for() {
+	use std::{sync::Arc, time::Duration};
+
+	let portlist1 = Arc::new(PortList::new(vec![Port::create_out_port::<i32>("p1a")]));
+	let portlist2 = PortList::new(vec![Port::create_in_port::<i32>("p2a")]);
+	assert!(
+		portlist2
+			.bind_to::<i32>("p2a", &*portlist1, "p1a")
+			.is_ok()
+	);
+
+	assert!(
+		portlist2
+			.wait_for::<i32>("p2x", 0, Duration::ZERO)
+			.is_err()
+	);
+	assert!(
+		portlist2
+			.wait_for::<f64>("p2a", 0, Duration::ZERO)
+			.is_err()
+	);
+	assert!(
+		portlist2
+			.wait_for::<i32>("p2a", 0, Duration::from_millis(10))
+			.is_err()
+	);
+
+	let writer = portlist1.clone();
+	let handle = std::thread::spawn(move || {
+		std::thread::sleep(Duration::from_millis(50));
+		writer.set::<i32>("p1a", 42).unwrap();
+	});
+	assert_eq!(
+		portlist2
+			.wait_for::<i32>("p2a", 0, Duration::from_secs(5))
+			.unwrap(),
+		1
+	);
+	assert_eq!(portlist2.get::<i32>("p2a").unwrap(), 42);
+	handle.join().unwrap();
+}
+
+#[test]
+fn port_names() {
+	let input = PortName::new("input");
+	let output = PortName::new("output");
+	let list = PortList::with_index(vec![
+		Port::create_in_port::<i32>(input),
+		Port::create_out_port::<i32>(output),
+	]);
+	assert_eq!(list.find(input).unwrap().name().as_ref(), "input");
+	assert_eq!(list.find_index(&output), Some(1));
+
+	list.bind_to::<i32>(input, &list, output).unwrap();
+	list.set::<i32>(output, 42).unwrap();
+	assert_eq!(list.get::<i32>(input).unwrap(), 42);
+
+	let mut db = PortDataBase::default();
+	db.create::<i32>(input, 1).unwrap();
+	assert_eq!(db.get::<i32>(PortName::new("input")).unwrap(), 1);
+	assert!(db.contains_key(&input));
+}
+
+#[test]
+fn filtered_iteration() {
+	let names = |ports: &mut dyn Iterator<Item = &Port>| ports.map(|port| port.name()).collect::<Vec<_>>();
+
+	let sources = PortList::new(vec![Port::create_out_port::<f64>("o1")]);
+	let list = PortList::new(vec![
+		Port::create_in_port::<f64>("i1"),
+		Port::create_in_port::<i32>("i2"),
+		Port::create_inout_port::<f64>("io1"),
+		Port::create_out_port::<f64>("o1"),
+		Port::create_out_queue_port::<i32>("q1", 2, OverflowPolicy::DropOldest),
+	]);
+	assert_eq!(names(&mut list.inputs()), ["i1".into(), "i2".into(), "io1".into()]);
+	assert_eq!(names(&mut list.outputs()), ["io1".into(), "o1".into(), "q1".into()]);
+	assert_eq!(names(&mut list.of_type::<i32>()), ["i2".into(), "q1".into()]);
+	assert_eq!(list.of_type::<String>().count(), 0);
+
+	list.bind_to::<f64>("i1", &sources, "o1").unwrap();
+	assert_eq!(names(&mut list.unbound_inputs()), ["i2".into(), "io1".into()]);
+
+	let array = PortArray::new([
+		Port::create_in_port::<f64>("i1"),
+		Port::create_out_port::<f64>("o1"),
+	]);
+	assert_eq!(names(&mut array.inputs()), ["i1".into()]);
+	assert_eq!(names(&mut array.outputs()), ["o1".into()]);
+
+	let mut db = PortDataBase::default();
+	db.create::<f64>("a", 1.0).unwrap();
+	db.create::<i32>("b", 1).unwrap();
+	assert_eq!(db.inputs().count(), 2);
+	assert_eq!(names(&mut db.of_type::<f64>()), ["a".into()]);
+}
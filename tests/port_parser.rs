@@ -0,0 +1,120 @@
+// Copyright © 2025 Stephan Kunz
+//! Test parsing port values from strings.
+
+use std::{f64::consts::PI, str::FromStr};
+
+use dataport::*;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Position {
+	x: f64,
+	y: f64,
+}
+
+impl FromStr for Position {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (x, y) = s
+			.split_once(',')
+			.ok_or_else(|| String::from("expected 'x,y'"))?;
+		Ok(Self {
+			x: x.parse().map_err(|_| String::from("invalid x"))?,
+			y: y.parse().map_err(|_| String::from("invalid y"))?,
+		})
+	}
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Mode(u8);
+
+#[test]
+fn builtin_parsers() {
+	let list = port_list![in a: f64, inout b: Vec<i32>, out c: String, in d: bool, in e: u8];
+	list.set_str("a", "3.141592653589793").unwrap();
+	assert_eq!(list.get::<f64>("a").unwrap(), PI);
+	list.set_str("b", "1;2;3").unwrap();
+	assert_eq!(list.get::<Vec<i32>>("b").unwrap(), vec![1, 2, 3]);
+	list.set_str("c", "hello").unwrap();
+	assert_eq!(list.get::<String>("c").unwrap(), "hello");
+	list.set_str("d", "true").unwrap();
+	assert!(list.get::<bool>("d").unwrap());
+
+	let Err(Error::ParseFailed {
+		port,
+		value,
+		expected,
+		reason,
+	}) = list.set_str("e", "256")
+	else {
+		panic!("parsing did not fail");
+	};
+	assert_eq!(port.as_ref(), "e");
+	assert_eq!(value.as_ref(), "256");
+	assert_eq!(expected, "u8");
+	assert_eq!(reason.as_ref(), "number too large to fit in target type");
+	assert!(list.get::<u8>("e").is_err());
+
+	assert!(matches!(list.set_str("x", "1"), Err(Error::NotFound { .. })));
+	let queue = Port::create_in_queue_port::<i32>("queue");
+	assert!(matches!(queue.set_from_str("1"), Err(Error::NotSettable { .. })));
+}
+
+#[test]
+fn lock_free_and_derived_values() {
+	let lock_free = Port::create_lock_free_out_port::<i32>("lock_free");
+	lock_free.set_from_str("7").unwrap();
+	let inputs = PortList::new(vec![Port::create_lock_free_in_port::<i32>("lock_free")]);
+	let outputs = PortList::new(vec![lock_free]);
+	inputs
+		.bind_to::<i32>("lock_free", &outputs, "lock_free")
+		.unwrap();
+	assert_eq!(inputs.get::<i32>("lock_free").unwrap(), 7);
+	assert!(matches!(inputs.set_str("lock_free", "8"), Err(Error::LockFree { .. })));
+
+	// a converted or computed value would drop the parsed value
+	let outputs = port_list![out single: f32];
+	let inputs = port_list![in double: f64];
+	inputs
+		.bind_to_converted::<f32, f64>("double", &outputs, "single")
+		.unwrap();
+	outputs.set::<f32>("single", 1.5).unwrap();
+	assert!(matches!(inputs.set_str("double", "2.5"), Err(Error::NotSettable { .. })));
+	assert_eq!(inputs.get::<f64>("double").unwrap(), 1.5);
+
+	let computed = Port::from(ComputedPort::new("computed", [], |_| Some(1)));
+	assert!(matches!(computed.set_from_str("2"), Err(Error::NotSettable { .. })));
+}
+
+#[test]
+fn registered_parsers() {
+	let list = port_list![in position: Position, in positions: Vec<Position>, in mode: Mode];
+	assert!(matches!(
+		list.set_str("position", "1.0,2.0"),
+		Err(Error::ParseFailed { reason, .. }) if reason.as_ref() == "no parser registered"
+	));
+
+	Port::register_from_str::<Position>();
+	list.set_str("position", "1.0,2.0").unwrap();
+	assert_eq!(list.get::<Position>("position").unwrap(), Position { x: 1.0, y: 2.0 });
+	list.set_str("positions", "1,2;3,4").unwrap();
+	assert_eq!(
+		list.get::<Vec<Position>>("positions")
+			.unwrap()
+			.len(),
+		2
+	);
+	assert!(matches!(
+		list.set_str("position", "1.0"),
+		Err(Error::ParseFailed { reason, .. }) if reason.as_ref() == "expected 'x,y'"
+	));
+
+	Port::register_parser(|value: &str| match value {
+		"fast" => Ok(Mode(2)),
+		"slow" => Ok(Mode(1)),
+		_ => Err("unknown mode"),
+	});
+	list.set_str("mode", "fast").unwrap();
+	assert_eq!(list.get::<Mode>("mode").unwrap(), Mode(2));
+	assert!(list.set_str("mode", "medium").is_err());
+}
@@ -0,0 +1,124 @@
+// Copyright © 2025 Stephan Kunz
+//! Test queue ports.
+
+use std::sync::{
+	Arc,
+	atomic::{AtomicU32, Ordering},
+};
+
+use dataport::*;
+
+#[derive(Ports)]
+struct Producer {
+	events: OutBoundQueuePort<String>,
+}
+
+#[derive(Ports)]
+struct Consumer {
+	#[port(name = "input")]
+	events: InBoundQueuePort<String>,
+}
+
+#[test]
+fn queue_in_port_list() {
+	let producer = PortList::new(vec![Port::create_out_queue_port::<i32>(
+		"out",
+		2,
+		OverflowPolicy::Error,
+	)]);
+	let consumer = PortList::new(vec![
+		Port::create_in_queue_port::<i32>("in"),
+		Port::create_in_port::<i32>("plain"),
+	]);
+	assert!(
+		consumer
+			.bind_to::<i32>("plain", &producer, "out")
+			.is_err()
+	);
+	assert!(
+		consumer
+			.bind_to::<f64>("in", &producer, "out")
+			.is_err()
+	);
+	consumer
+		.bind_to::<i32>("in", &producer, "out")
+		.unwrap();
+	assert!(
+		consumer
+			.bind_to::<i32>("in", &producer, "out")
+			.is_err()
+	);
+
+	let count = Arc::new(AtomicU32::new(0));
+	let counter = count.clone();
+	consumer
+		.on_change::<i32>("in", move |_, _| {
+			counter.fetch_add(1, Ordering::Relaxed);
+		})
+		.unwrap();
+
+	assert_eq!(consumer.pop::<i32>("in").unwrap(), None);
+	producer.push::<i32>("out", 1).unwrap();
+	producer.push::<i32>("out", 2).unwrap();
+	assert!(matches!(producer.push::<i32>("out", 3), Err(Error::QueueFull { .. })));
+	assert!(producer.push::<f64>("out", 3.0).is_err());
+	assert!(consumer.push::<i32>("in", 3).is_err());
+	assert_eq!(count.load(Ordering::Relaxed), 2);
+	assert_eq!(consumer.sequence_number("in").unwrap(), 2);
+
+	assert_eq!(consumer.pop::<i32>("in").unwrap(), Some(1));
+	assert_eq!(consumer.pop::<i32>("in").unwrap(), Some(2));
+	assert_eq!(consumer.pop::<i32>("in").unwrap(), None);
+	assert!(consumer.pop::<f64>("in").is_err());
+	assert!(producer.pop::<i32>("out").is_err());
+
+	consumer.unbind::<i32>("in").unwrap();
+	producer.push::<i32>("out", 4).unwrap();
+	assert_eq!(consumer.pop::<i32>("in").unwrap(), None);
+	consumer
+		.rebind_to::<i32>("in", &producer, "out")
+		.unwrap();
+	assert_eq!(consumer.pop::<i32>("in").unwrap(), Some(4));
+}
+
+#[test]
+fn overflow_policies() {
+	let drop_oldest = OutBoundQueuePort::<i32>::new("oldest", 2, OverflowPolicy::DropOldest);
+	let drop_newest = OutBoundQueuePort::<i32>::new("newest", 2, OverflowPolicy::DropNewest);
+	let mut oldest = InBoundQueuePort::<i32>::new("in1");
+	let mut newest = InBoundQueuePort::<i32>::new("in2");
+	oldest.bind_to_queue_port(&drop_oldest).unwrap();
+	newest.bind_to_queue_port(&drop_newest).unwrap();
+
+	for value in 1..=3 {
+		drop_oldest.push(value).unwrap();
+		drop_newest.push(value).unwrap();
+	}
+	assert_eq!(oldest.drain(), [2, 3]);
+	assert_eq!(newest.drain(), [1, 2]);
+	// a dropped value does not count as a change
+	assert_eq!(drop_oldest.sequence_number(), 3);
+	assert_eq!(drop_newest.sequence_number(), 2);
+}
+
+#[test]
+fn derived_queue_ports() {
+	let producer = Producer {
+		events: OutBoundQueuePort::new("events", 4, OverflowPolicy::default()),
+	};
+	let consumer = Consumer {
+		events: InBoundQueuePort::new("input"),
+	};
+	consumer
+		.bind_to::<String>("input", &producer, "events")
+		.unwrap();
+	producer.events.push("first").unwrap();
+	producer
+		.push::<String>("events", "second")
+		.unwrap();
+	assert_eq!(consumer.events.len(), 2);
+	assert_eq!(consumer.events.peek().unwrap(), "first");
+	assert_eq!(consumer.events.pop().unwrap(), "first");
+	assert_eq!(consumer.pop::<String>("input").unwrap().unwrap(), "second");
+	assert!(consumer.events.is_empty());
+}
@@ -0,0 +1,88 @@
+// Copyright © 2025 Stephan Kunz
+//! Test consistent reading of several ports.
+
+use std::sync::Arc;
+
+use dataport::*;
+
+#[test]
+fn read_many() {
+	let list = port_list![out x: f64 = 1.0, out y: f64 = 2.0, in label: String, inout path: Vec<i32> = vec![1, 2]];
+	let (x, y, path) = list
+		.read_many::<(f64, f64, Vec<i32>)>(("x", "y", "path"))
+		.unwrap();
+	assert_eq!((*x, *y), (1.0, 2.0));
+	assert_eq!(*path, vec![1, 2]);
+	assert_eq!(x.sequence_number(), 1);
+	// readers do not block each other
+	assert_eq!(list.get::<f64>("x").unwrap(), 1.0);
+	assert!(matches!(list.try_write::<f64>("x"), Err(Error::IsLocked { .. })));
+	drop((x, y, path));
+
+	list.set::<f64>("y", 3.0).unwrap();
+	let ((x, x_seq), (y, y_seq)) = list.snapshot::<(f64, f64)>(("x", "y")).unwrap();
+	assert_eq!((x, x_seq), (1.0, 1));
+	assert_eq!((y, y_seq), (3.0, 2));
+
+	assert!(matches!(
+		list.read_many::<(f64, f64)>(("x", "z")),
+		Err(Error::NotFound { .. })
+	));
+	assert!(matches!(
+		list.read_many::<(f64, i32)>(("x", "y")),
+		Err(Error::NoValueSet { .. })
+	));
+	assert!(matches!(
+		list.snapshot::<(f64, String)>(("x", "label")),
+		Err(Error::NoValueSet { .. })
+	));
+	// no lock is left behind on errors
+	assert!(list.try_write::<f64>("x").is_ok());
+	assert!(list.try_write::<f64>("y").is_ok());
+}
+
+#[test]
+fn consistency_with_transactions() {
+	let mut pb = PortDataBase::default();
+	pb.create::<i32>("x", 0).unwrap();
+	pb.create::<i32>("y", 0).unwrap();
+	pb.create::<i32>("theta", 0).unwrap();
+	let pb = Arc::new(pb);
+
+	let writer = {
+		let pb = pb.clone();
+		std::thread::spawn(move || {
+			for n in 1..=1000 {
+				pb.transaction(|tx| {
+					tx.set::<i32>("theta", n)?;
+					tx.set::<i32>("y", n)?;
+					tx.set::<i32>("x", n)
+				})
+				.unwrap();
+			}
+		})
+	};
+	let readers: Vec<_> = (0..2)
+		.map(|_| {
+			let pb = pb.clone();
+			std::thread::spawn(move || {
+				for _ in 0..1000 {
+					let ((x, x_seq), (y, y_seq), (theta, _)) = pb
+						.snapshot::<(i32, i32, i32)>(("x", "y", "theta"))
+						.unwrap();
+					assert_eq!(x, y);
+					assert_eq!(y, theta);
+					assert_eq!(x_seq, y_seq);
+				}
+			})
+		})
+		.collect();
+	writer.join().unwrap();
+	for reader in readers {
+		reader.join().unwrap();
+	}
+	let (x, theta) = pb
+		.read_many::<(i32, i32)>(("x", "theta"))
+		.unwrap();
+	assert_eq!((*x, *theta), (1000, 1000));
+}
@@ -0,0 +1,87 @@
+// Copyright © 2025 Stephan Kunz
+//! Test [`ScopedPortDataBase`]
+
+use std::sync::Arc;
+
+use dataport::*;
+
+#[test]
+fn parent_fallback() {
+	let root = Arc::new(ScopedPortDataBase::default());
+	root.create::<i32>("a", 1).unwrap();
+	root.create::<i32>("b", 2).unwrap();
+
+	let child = ScopedPortDataBase::with_parent(root.clone());
+	assert!(child.parent().is_some());
+	assert!(child.contains_key("a"));
+	assert!(!child.contains_local_key("a"));
+	assert_eq!(child.get::<i32>("a").unwrap(), 1);
+
+	// writes through the child are visible in the parent
+	child.set::<i32>("a", 11).unwrap();
+	assert_eq!(root.get::<i32>("a").unwrap(), 11);
+
+	// a local key shadows the parents key
+	child.create::<i32>("b", 22).unwrap();
+	assert_eq!(child.get::<i32>("b").unwrap(), 22);
+	assert_eq!(root.get::<i32>("b").unwrap(), 2);
+	assert_eq!(child.delete::<i32>("b").unwrap(), 22);
+	assert_eq!(child.get::<i32>("b").unwrap(), 2);
+
+	// keys created later in the parent are found too
+	root.create::<f64>("c", 3.0).unwrap();
+	assert_eq!(child.update::<f64>("c", 4.0).unwrap(), Some(3.0));
+	assert_eq!(root.get::<f64>("c").unwrap(), 4.0);
+	assert!(child.update::<i32>("c", 4).is_err());
+	assert!(child.port("d").is_err());
+	assert!(!root.contains_key("d"));
+}
+
+#[test]
+fn remapping() {
+	let root = Arc::new(ScopedPortDataBase::default());
+	root.create::<String>("goal", "home").unwrap();
+
+	let mut child = ScopedPortDataBase::with_parent(root.clone());
+	child.remap("target", "goal").unwrap();
+	child.remap("result", "outcome").unwrap();
+	assert!(child.remap("target", "other").is_err());
+	assert!(
+		ScopedPortDataBase::default()
+			.remap("a", "b")
+			.is_err()
+	);
+	assert_eq!(child.remapping("target").unwrap().as_ref(), "goal");
+	assert!(child.remapping("goal").is_none());
+
+	assert_eq!(child.get::<String>("target").unwrap(), "home");
+	assert_eq!(child.port("target").unwrap().name().as_ref(), "goal");
+	child.set::<String>("target", "dock").unwrap();
+	assert_eq!(root.get::<String>("goal").unwrap(), "dock");
+
+	// remapped keys are created and deleted in the parent
+	child.create::<bool>("result", true).unwrap();
+	assert!(!child.contains_local_key("result"));
+	assert!(root.get::<bool>("outcome").unwrap());
+	assert!(child.delete::<bool>("result").unwrap());
+	assert!(!root.contains_key("outcome"));
+
+	// remappings are resolved through several scopes
+	let mut grandchild = ScopedPortDataBase::with_parent(Arc::new(child));
+	grandchild.remap("destination", "target").unwrap();
+	assert_eq!(grandchild.get::<String>("destination").unwrap(), "dock");
+}
+
+#[test]
+fn binding() {
+	let root = Arc::new(ScopedPortDataBase::default());
+	root.create::<i32>("value", 42).unwrap();
+	let mut child = ScopedPortDataBase::with_parent(root.clone());
+	child.remap("input", "value").unwrap();
+
+	let list = port_list![in a: i32];
+	list.bind_to::<i32>("a", &child, "input").unwrap();
+	assert_eq!(list.get::<i32>("a").unwrap(), 42);
+	root.set::<i32>("value", 24).unwrap();
+	assert_eq!(list.get::<i32>("a").unwrap(), 24);
+}
@@ -0,0 +1,141 @@
+// Copyright © 2025 Stephan Kunz
+//! Test serde support of [`PortDataBase`]
+
+#![cfg(feature = "serde")]
+
+use dataport::*;
+
+fn registry() -> TypeRegistry {
+	let mut registry = TypeRegistry::default();
+	assert!(registry.register::<i32>("i32").is_ok());
+	assert!(registry.register::<f64>("f64").is_ok());
+	assert!(registry.register::<String>("string").is_ok());
+	assert!(registry.register::<Vec<u8>>("bytes").is_ok());
+	registry
+}
+
+#[test]
+fn registration() {
+	let mut registry = registry();
+	assert!(matches!(
+		registry.register::<bool>("i32"),
+		Err(Error::TagAlreadyRegistered { tag }) if tag.as_ref() == "i32"
+	));
+	assert!(matches!(
+		registry.register::<i32>("int"),
+		Err(Error::TagAlreadyRegistered { tag }) if tag.as_ref() == "i32"
+	));
+	assert!(registry.register_key::<bool>("flag").is_ok());
+	assert!(registry.register_key::<i32>("flag").is_err());
+}
+
+#[test]
+fn round_trip() {
+	let registry = registry();
+	let mut db = PortDataBase::default();
+	db.create::<i32>("a", 42).unwrap();
+	db.create::<f64>("b", 3.5).unwrap();
+	db.create::<String>("c", "hello").unwrap();
+	db.create::<Vec<u8>>("d", vec![1, 2, 3]).unwrap();
+
+	let json = serde_json::to_string(&db.serializable(&registry)).unwrap();
+	assert_eq!(
+		json,
+		r#"{"a":{"type":"i32","value":42},"b":{"type":"f64","value":3.5},"c":{"type":"string","value":"hello"},"d":{"type":"bytes","value":[1,2,3]}}"#
+	);
+
+	let restored = PortDataBase::deserialize_with(&registry, &mut serde_json::Deserializer::from_str(&json)).unwrap();
+	assert_eq!(restored.get::<i32>("a").unwrap(), 42);
+	assert_eq!(restored.get::<f64>("b").unwrap(), 3.5);
+	assert_eq!(restored.get::<String>("c").unwrap(), "hello");
+	assert_eq!(restored.get::<Vec<u8>>("d").unwrap(), vec![1, 2, 3]);
+}
+
+#[test]
+fn keyed_values() {
+	let mut registry = registry();
+	registry.register_key::<bool>("flag").unwrap();
+	registry.register_key::<u64>("count").unwrap();
+
+	let mut db = PortDataBase::default();
+	db.create::<bool>("flag", true).unwrap();
+	db.create::<i32>("a", 1).unwrap();
+	let json = serde_json::to_string(&db.serializable(&registry)).unwrap();
+	assert_eq!(json, r#"{"a":{"type":"i32","value":1},"flag":true}"#);
+
+	let config = r#"{"count":7,"flag":false,"a":{"type":"i32","value":2},"empty":{"type":"string","value":null}}"#;
+	db.load_from(&registry, &mut serde_json::Deserializer::from_str(config))
+		.unwrap();
+	assert_eq!(db.get::<u64>("count").unwrap(), 7);
+	assert!(!db.get::<bool>("flag").unwrap());
+	assert_eq!(db.get::<i32>("a").unwrap(), 2);
+	assert_eq!(db.sequence_number("a").unwrap(), 2);
+	assert!(db.contains::<String>("empty").unwrap());
+	assert!(db.get::<String>("empty").is_err());
+}
+
+#[test]
+fn errors() {
+	let registry = registry();
+	let mut db = PortDataBase::default();
+	db.create::<bool>("flag", true).unwrap();
+	assert!(serde_json::to_string(&db.serializable(&registry)).is_err());
+
+	let mut db = PortDataBase::default();
+	db.create::<i32>("a", 1).unwrap();
+	let wrong_type = r#"{"a":{"type":"f64","value":2.0}}"#;
+	assert!(
+		db.load_from(&registry, &mut serde_json::Deserializer::from_str(wrong_type))
+			.is_err()
+	);
+	let unknown_tag = r#"{"b":{"type":"bool","value":true}}"#;
+	assert!(
+		db.load_from(&registry, &mut serde_json::Deserializer::from_str(unknown_tag))
+			.is_err()
+	);
+	let missing_tag = r#"{"b":{"value":true}}"#;
+	assert!(
+		db.load_from(&registry, &mut serde_json::Deserializer::from_str(missing_tag))
+			.is_err()
+	);
+	let duplicate_tag = r#"{"b":{"type":"i32","type":"i32","value":1}}"#;
+	assert!(
+		db.load_from(&registry, &mut serde_json::Deserializer::from_str(duplicate_tag))
+			.is_err()
+	);
+
+	// a failed load leaves the database unchanged
+	let partly_valid = r#"{"a":{"type":"i32","value":2},"b":{"type":"f64","value":1.0},"c":{"type":"f64","value":"text"}}"#;
+	assert!(
+		db.load_from(&registry, &mut serde_json::Deserializer::from_str(partly_valid))
+			.is_err()
+	);
+	let partly_wrong = r#"{"b":{"type":"f64","value":1.0},"a":{"type":"f64","value":2.0}}"#;
+	assert!(
+		db.load_from(&registry, &mut serde_json::Deserializer::from_str(partly_wrong))
+			.is_err()
+	);
+	assert_eq!(db.get::<i32>("a").unwrap(), 1);
+	assert_eq!(db.sequence_number("a").unwrap(), 1);
+	assert!(db.port("b").is_err());
+	assert!(db.port("c").is_err());
+}
+
+#[test]
+fn field_order() {
+	let registry = registry();
+	let mut db = PortDataBase::default();
+	let config = r#"{"a":{"value":42,"type":"i32"},"b":{"value":[1,2],"type":"bytes"},"c":{"value":null,"type":"string"}}"#;
+	db.load_from(&registry, &mut serde_json::Deserializer::from_str(config))
+		.unwrap();
+	assert_eq!(db.get::<i32>("a").unwrap(), 42);
+	assert_eq!(db.get::<Vec<u8>>("b").unwrap(), vec![1, 2]);
+	assert!(db.contains::<String>("c").unwrap());
+	assert!(db.get::<String>("c").is_err());
+
+	let wrong_value = r#"{"d":{"value":"text","type":"i32"}}"#;
+	assert!(
+		db.load_from(&registry, &mut serde_json::Deserializer::from_str(wrong_value))
+			.is_err()
+	);
+}